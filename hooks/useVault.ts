@@ -10,7 +10,8 @@ import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
   getAssociatedTokenAddressSync,
   getAccount,
-  createAssociatedTokenAccountInstruction
+  createAssociatedTokenAccountInstruction,
+  createAssociatedTokenAccountIdempotentInstruction
 } from '@solana/spl-token';
 import { DefiVault } from '@/types/defi_vault';
 import { PROGRAM_ID, SUPPORTED_TOKENS, INTEREST_RATES, MIN_DEPOSITS } from '@/utils/constants';
@@ -120,17 +121,23 @@ export function useVault(tokenSymbol: string) {
         );
         
         if (mountedRef.current && userPositionAccount) {
+          // Project vault-wide interest to now, then value the position's shares (matches Vault::projected)
           const currentTime = Math.floor(Date.now() / 1000);
-          const timeElapsed = currentTime - userPositionAccount.lastUpdateTime.toNumber();
-          const additionalInterest = calculateInterest(
-            userPositionAccount.depositedAmount.toNumber(),
+          const timeElapsed = currentTime - vaultAccount.lastAccrualTime.toNumber();
+          const pendingInterest = calculateInterest(
+            vaultAccount.totalDeposited.toNumber(),
             vaultAccount.interestRate.toNumber(),
             timeElapsed
           );
+          const totalAssets = vaultAccount.totalDeposited.toNumber() + vaultAccount.totalAccruedInterest.toNumber() + pendingInterest;
+          const totalShares = vaultAccount.totalShares.toNumber();
+          const shares = userPositionAccount.shares.toNumber();
+          const positionValue = totalShares === 0 ? shares : Math.floor((shares * totalAssets) / totalShares);
+          const depositedAmount = userPositionAccount.depositedAmount.toNumber();
 
           setUserPosition({
-            deposited_amount: userPositionAccount.depositedAmount.toNumber(),
-            accrued_interest: userPositionAccount.accruedInterest.toNumber() + additionalInterest,
+            deposited_amount: depositedAmount,
+            accrued_interest: Math.max(positionValue - depositedAmount, 0),
             last_update_time: userPositionAccount.lastUpdateTime.toNumber(),
             deposit_count: userPositionAccount.depositCount.toNumber(),
             withdraw_count: userPositionAccount.withdrawCount.toNumber(),
@@ -268,15 +275,26 @@ export function useVault(tokenSymbol: string) {
           [Buffer.from('vault-token'), tokenMint.toBuffer()],
           PROGRAM_ID
       );
+      const [rewardReservePda] = PublicKey.findProgramAddressSync(
+          [Buffer.from('vault-reserve'), tokenMint.toBuffer()],
+          PROGRAM_ID
+      );
+
+      const treasury = getAssociatedTokenAddressSync(tokenMint, publicKey, false, tokenProgramId);
+      instructions.push(
+        createAssociatedTokenAccountIdempotentInstruction(publicKey, treasury, publicKey, tokenMint, tokenProgramId, ASSOCIATED_TOKEN_PROGRAM_ID)
+      );
 
       instructions.push(
         await program.methods
-          .initializeVault(new BN(interestRate), minDeposit)
+          .initializeVault(new BN(0), new BN(interestRate), minDeposit, { simple: {} })
           .accounts({
             vault: vaultPda,
             authority: publicKey,
             tokenMint: tokenMint,
             tokenVault: vaultTokenPda,
+            rewardReserve: rewardReservePda,
+            treasury: treasury,
             tokenProgram: tokenProgramId,
             systemProgram: SystemProgram.programId,
             rent: web3.SYSVAR_RENT_PUBKEY,
@@ -336,6 +354,7 @@ export function useVault(tokenSymbol: string) {
                     vault: vaultPda,
                     userPosition: userPositionPda,
                     user: publicKey,
+                    tokenMint: tokenMint,
                     userTokenAccount: userTokenAccount, // This now correctly points to the user's wallet for SOL
                     vaultTokenAccount: vaultTokenPda,
                     tokenProgram: tokenProgramId,
@@ -382,6 +401,7 @@ export function useVault(tokenSymbol: string) {
 
         const [vaultPda] = PublicKey.findProgramAddressSync([Buffer.from('vault'), tokenMint.toBuffer()], PROGRAM_ID);
         const [vaultTokenPda] = PublicKey.findProgramAddressSync([Buffer.from('vault-token'), tokenMint.toBuffer()], PROGRAM_ID);
+        const [rewardReservePda] = PublicKey.findProgramAddressSync([Buffer.from('vault-reserve'), tokenMint.toBuffer()], PROGRAM_ID);
         const [userPositionPda] = PublicKey.findProgramAddressSync([Buffer.from('user-position'), vaultPda.toBuffer(), publicKey.toBuffer()], PROGRAM_ID);
         
         const userTokenAccount = (token.symbol === 'SOL')
@@ -394,8 +414,10 @@ export function useVault(tokenSymbol: string) {
                 vault: vaultPda,
                 userPosition: userPositionPda,
                 user: publicKey,
+                tokenMint: tokenMint,
                 userTokenAccount: userTokenAccount,
                 vaultTokenAccount: vaultTokenPda,
+                rewardReserve: rewardReservePda,
                 tokenProgram: tokenProgramId,
             })
             .instruction();
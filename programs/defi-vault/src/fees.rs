@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::interest::BPS_DENOMINATOR;
+use crate::safe_math::{to_u64, SafeMath};
+use crate::VaultError;
+
+/// Protocol fees charged by a vault, all in basis points
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct FeeConfig {
+    pub entry_fee_bps: u16, // on each deposit
+    pub exit_fee_bps: u16, // on each withdrawal
+    pub performance_fee_bps: u16, // cut of accrued interest
+    pub management_fee_bps: u16, // annual, on total assets
+    pub flash_loan_fee_bps: u16, // on each flash loan
+}
+
+impl FeeConfig {
+    pub const LEN: usize = 2 + 2 + 2 + 2 + 2;
+
+    pub fn validate(&self) -> Result<()> {
+        for bps in [
+            self.entry_fee_bps,
+            self.exit_fee_bps,
+            self.performance_fee_bps,
+            self.management_fee_bps,
+            self.flash_loan_fee_bps,
+        ] {
+            require!(bps as u128 <= BPS_DENOMINATOR, VaultError::InvalidFee);
+        }
+        Ok(())
+    }
+}
+
+/// Which fee a `FeeChargedEvent` reports
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FeeKind {
+    Entry,
+    Exit,
+    Performance,
+    Management,
+    FlashLoan,
+}
+
+/// `amount * bps / 10_000`, rounded down
+pub fn bps_of(amount: u64, bps: u16) -> Result<u64> {
+    to_u64(
+        (amount as u128)
+            .safe_mul(bps as u128)?
+            .safe_div(BPS_DENOMINATOR)?,
+    )
+}
@@ -0,0 +1,369 @@
+use anchor_lang::prelude::*;
+
+use crate::safe_math::{to_u64, SafeMath};
+use crate::VaultError;
+
+pub const SECONDS_PER_YEAR: u128 = 365 * 24 * 60 * 60;
+pub const BPS_DENOMINATOR: u128 = 10_000;
+
+/// Fixed-point scale used for compounding math (1.0 == WAD)
+pub const WAD: u128 = 1_000_000_000_000_000_000;
+
+/// Upper bound on Taylor series terms for `exp_wad`; 1.0 converges well within this
+const MAX_EXP_TERMS: u128 = 32;
+
+/// How a vault turns its annual rate into accrued interest
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InterestModel {
+    /// Interest on principal only
+    Simple,
+    /// Interest on principal plus accrued interest, compounded once every `period` seconds.
+    /// A trailing partial period earns simple interest pro rata.
+    Compound { period: i64 },
+    /// Interest compounded continuously, `assets * (e^(rate * t) - 1)`
+    Continuous,
+}
+
+impl InterestModel {
+    pub const LEN: usize = 1 + 8;
+
+    pub fn validate(&self) -> Result<()> {
+        if let InterestModel::Compound { period } = self {
+            require!(*period > 0, VaultError::InvalidInterestModel);
+        }
+        Ok(())
+    }
+
+    /// Interest owed over `time_elapsed` seconds.
+    ///
+    /// Compound credits the leftover seconds after the last whole period pro rata too, so
+    /// assets only earn for the time they were actually in the vault.
+    pub fn accrue(
+        &self,
+        principal: u64,
+        total_assets: u64,
+        interest_rate_bps: u64,
+        time_elapsed: i64,
+    ) -> Result<u64> {
+        if time_elapsed <= 0 {
+            return Ok(0);
+        }
+
+        match *self {
+            InterestModel::Simple => {
+                calculate_interest(principal, interest_rate_bps, time_elapsed)
+            }
+            InterestModel::Compound { period } => {
+                let periods = time_elapsed.safe_div(period)?;
+                let interest =
+                    compound_interest(total_assets, interest_rate_bps, period, periods as u64)?;
+                let remainder = calculate_interest(
+                    total_assets.safe_add(interest)?,
+                    interest_rate_bps,
+                    time_elapsed.safe_sub(periods.safe_mul(period)?)?,
+                )?;
+                interest.safe_add(remainder)
+            }
+            InterestModel::Continuous => {
+                continuous_interest(total_assets, interest_rate_bps, time_elapsed)
+            }
+        }
+    }
+}
+
+/// Kinked utilization curve: the rate climbs gently up to `optimal_utilization_bps`
+/// and steeply past it. All parameters are in basis points.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RateCurve {
+    pub base_rate_bps: u64, // rate at zero utilization
+    pub slope1_bps: u64, // added across 0..optimal utilization
+    pub optimal_utilization_bps: u64, // the kink
+    pub slope2_bps: u64, // added across optimal..full utilization
+}
+
+impl RateCurve {
+    pub const LEN: usize = 8 + 8 + 8 + 8;
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.optimal_utilization_bps > 0 && self.optimal_utilization_bps as u128 <= BPS_DENOMINATOR,
+            VaultError::InvalidRateCurve
+        );
+        Ok(())
+    }
+
+    /// Annual rate in basis points at `utilization_bps`
+    pub fn rate(&self, utilization_bps: u64) -> Result<u64> {
+        let optimal = self.optimal_utilization_bps;
+        if utilization_bps <= optimal {
+            return self
+                .base_rate_bps
+                .safe_add(self.slope1_bps.safe_mul(utilization_bps)?.safe_div(optimal)?);
+        }
+
+        let excess = utilization_bps.safe_sub(optimal)?;
+        let excess_range = (BPS_DENOMINATOR as u64).safe_sub(optimal)?;
+        self.base_rate_bps
+            .safe_add(self.slope1_bps)?
+            .safe_add(self.slope2_bps.safe_mul(excess)?.safe_div(excess_range)?)
+    }
+}
+
+// Helper function to calculate interest
+pub fn calculate_interest(principal: u64, interest_rate_bps: u64, time_elapsed: i64) -> Result<u64> {
+    if principal == 0 || time_elapsed <= 0 {
+        return Ok(0);
+    }
+
+    // Simple interest calculation: (principal * rate * time) / (10000 * seconds_per_year)
+    // Rate is in basis points (1 basis point = 0.01%)
+    let interest = (principal as u128)
+        .safe_mul(interest_rate_bps as u128)?
+        .safe_mul(time_elapsed as u128)?
+        .safe_div(BPS_DENOMINATOR)?
+        .safe_div(SECONDS_PER_YEAR)?;
+
+    to_u64(interest)
+}
+
+/// `assets * ((1 + r)^periods - 1)` where `r` is the rate for one `period` of seconds
+pub fn compound_interest(
+    assets: u64,
+    interest_rate_bps: u64,
+    period: i64,
+    periods: u64,
+) -> Result<u64> {
+    if assets == 0 || periods == 0 {
+        return Ok(0);
+    }
+
+    let period_rate = (interest_rate_bps as u128)
+        .safe_mul(period as u128)?
+        .safe_mul(WAD)?
+        .safe_div(BPS_DENOMINATOR.safe_mul(SECONDS_PER_YEAR)?)?;
+    let growth = pow_wad(WAD.safe_add(period_rate)?, periods)?;
+
+    to_u64(
+        (assets as u128)
+            .safe_mul(growth.safe_sub(WAD)?)?
+            .safe_div(WAD)?,
+    )
+}
+
+/// `assets * (e^(r * t) - 1)` with `r` the annual rate
+pub fn continuous_interest(assets: u64, interest_rate_bps: u64, time_elapsed: i64) -> Result<u64> {
+    if assets == 0 || time_elapsed <= 0 {
+        return Ok(0);
+    }
+
+    let exponent = (interest_rate_bps as u128)
+        .safe_mul(time_elapsed as u128)?
+        .safe_mul(WAD)?
+        .safe_div(BPS_DENOMINATOR.safe_mul(SECONDS_PER_YEAR)?)?;
+    let growth = exp_wad(exponent)?;
+
+    to_u64(
+        (assets as u128)
+            .safe_mul(growth.safe_sub(WAD)?)?
+            .safe_div(WAD)?,
+    )
+}
+
+/// `index * (1 + r * t)` for a WAD-scaled borrow index, `r` the annual rate
+pub fn grow_index(index: u128, interest_rate_bps: u64, time_elapsed: i64) -> Result<u128> {
+    if time_elapsed <= 0 {
+        return Ok(index);
+    }
+
+    let growth = (interest_rate_bps as u128)
+        .safe_mul(time_elapsed as u128)?
+        .safe_mul(WAD)?
+        .safe_div(BPS_DENOMINATOR.safe_mul(SECONDS_PER_YEAR)?)?;
+    mul_wad(index, WAD.safe_add(growth)?)
+}
+
+/// `a * b` for WAD-scaled operands, splitting `a` so large products don't overflow early
+pub fn mul_wad(a: u128, b: u128) -> Result<u128> {
+    let whole = (a / WAD).safe_mul(b)?;
+    let fraction = (a % WAD).safe_mul(b)?.safe_div(WAD)?;
+    whole.safe_add(fraction)
+}
+
+/// `base^exp` for a WAD-scaled `base`, by repeated squaring
+pub fn pow_wad(base: u128, mut exp: u64) -> Result<u128> {
+    let mut result = WAD;
+    let mut base = base;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_wad(result, base)?;
+        }
+        exp >>= 1;
+        if exp > 0 {
+            base = mul_wad(base, base)?;
+        }
+    }
+    Ok(result)
+}
+
+/// `e^x` for a WAD-scaled, non-negative `x`.
+///
+/// Halves `x` until it is at most 1.0 so the Taylor series converges quickly without
+/// overflowing, then squares the result back up.
+pub fn exp_wad(x: u128) -> Result<u128> {
+    let mut reduced = x;
+    let mut halvings = 0u32;
+    while reduced > WAD {
+        reduced >>= 1;
+        halvings += 1;
+    }
+
+    let mut result = WAD;
+    let mut term = WAD;
+    let mut n = 1u128;
+    while n <= MAX_EXP_TERMS {
+        term = term.safe_mul(reduced)?.safe_div(WAD.safe_mul(n)?)?;
+        if term == 0 {
+            break;
+        }
+        result = result.safe_add(term)?;
+        n += 1;
+    }
+
+    for _ in 0..halvings {
+        result = mul_wad(result, result)?;
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: i64 = 24 * 60 * 60;
+    const YEAR: i64 = SECONDS_PER_YEAR as i64;
+    const PRINCIPAL: u64 = 1_000_000_000_000; // 1M tokens at 6 decimals
+
+    fn relative_error(actual: u64, expected: f64) -> f64 {
+        ((actual as f64) - expected).abs() / expected
+    }
+
+    #[test]
+    fn simple_matches_closed_form() {
+        let interest = InterestModel::Simple
+            .accrue(PRINCIPAL, PRINCIPAL, 500, YEAR)
+            .unwrap();
+        assert_eq!(interest, PRINCIPAL / 20);
+
+        // Simple ignores already-accrued interest
+        let interest = InterestModel::Simple
+            .accrue(PRINCIPAL, 2 * PRINCIPAL, 500, YEAR)
+            .unwrap();
+        assert_eq!(interest, PRINCIPAL / 20);
+    }
+
+    #[test]
+    fn compound_single_period_equals_simple() {
+        let model = InterestModel::Compound { period: YEAR };
+        let interest = model.accrue(PRINCIPAL, PRINCIPAL, 500, YEAR).unwrap();
+        assert_eq!(interest, PRINCIPAL / 20);
+    }
+
+    #[test]
+    fn compound_daily_matches_closed_form() {
+        let model = InterestModel::Compound { period: DAY };
+        let interest = model.accrue(PRINCIPAL, PRINCIPAL, 500, YEAR).unwrap();
+        let expected = (PRINCIPAL as f64) * ((1.0 + 0.05 / 365.0_f64).powi(365) - 1.0);
+        assert!(relative_error(interest, expected) < 1e-9, "{interest} vs {expected}");
+    }
+
+    #[test]
+    fn compound_accrues_partial_periods_pro_rata() {
+        let model = InterestModel::Compound { period: DAY };
+        let interest = model.accrue(PRINCIPAL, PRINCIPAL, 500, DAY / 2).unwrap();
+        assert_eq!(interest, calculate_interest(PRINCIPAL, 500, DAY / 2).unwrap());
+
+        let whole = model.accrue(PRINCIPAL, PRINCIPAL, 500, 3 * DAY).unwrap();
+        let remainder = calculate_interest(PRINCIPAL + whole, 500, DAY / 2).unwrap();
+        let interest = model.accrue(PRINCIPAL, PRINCIPAL, 500, 3 * DAY + DAY / 2).unwrap();
+        assert_eq!(interest, whole + remainder);
+    }
+
+    #[test]
+    fn continuous_matches_closed_form() {
+        let interest = InterestModel::Continuous
+            .accrue(PRINCIPAL, PRINCIPAL, 500, YEAR)
+            .unwrap();
+        let expected = (PRINCIPAL as f64) * (0.05_f64.exp() - 1.0);
+        assert!(relative_error(interest, expected) < 1e-9, "{interest} vs {expected}");
+    }
+
+    #[test]
+    fn continuous_handles_large_exponents() {
+        // 100% APR over ten years, e^10
+        let interest = continuous_interest(1_000_000, 10_000, 10 * YEAR).unwrap();
+        let expected = 1_000_000.0 * (10.0_f64.exp() - 1.0);
+        assert!(relative_error(interest, expected) < 1e-9, "{interest} vs {expected}");
+    }
+
+    #[test]
+    fn models_are_ordered_by_compounding_frequency() {
+        let simple = InterestModel::Simple.accrue(PRINCIPAL, PRINCIPAL, 1_000, YEAR).unwrap();
+        let monthly = InterestModel::Compound { period: YEAR / 12 }
+            .accrue(PRINCIPAL, PRINCIPAL, 1_000, YEAR)
+            .unwrap();
+        let continuous = InterestModel::Continuous
+            .accrue(PRINCIPAL, PRINCIPAL, 1_000, YEAR)
+            .unwrap();
+        assert!(simple < monthly);
+        assert!(monthly < continuous);
+    }
+
+    #[test]
+    fn zero_elapsed_accrues_nothing() {
+        for model in [
+            InterestModel::Simple,
+            InterestModel::Compound { period: DAY },
+            InterestModel::Continuous,
+        ] {
+            assert_eq!(model.accrue(PRINCIPAL, PRINCIPAL, 500, 0).unwrap(), 0);
+            assert_eq!(model.accrue(PRINCIPAL, PRINCIPAL, 500, -5).unwrap(), 0);
+        }
+    }
+
+    const CURVE: RateCurve = RateCurve {
+        base_rate_bps: 100,
+        slope1_bps: 400,
+        optimal_utilization_bps: 8_000,
+        slope2_bps: 6_000,
+    };
+
+    #[test]
+    fn rate_curve_is_linear_up_to_the_kink() {
+        assert_eq!(CURVE.rate(0).unwrap(), 100);
+        assert_eq!(CURVE.rate(4_000).unwrap(), 300);
+        assert_eq!(CURVE.rate(8_000).unwrap(), 500);
+    }
+
+    #[test]
+    fn rate_curve_is_steep_past_the_kink() {
+        assert_eq!(CURVE.rate(9_000).unwrap(), 3_500);
+        assert_eq!(CURVE.rate(10_000).unwrap(), 6_500);
+
+        let full_kink = RateCurve { optimal_utilization_bps: 10_000, ..CURVE };
+        assert_eq!(full_kink.rate(10_000).unwrap(), 500);
+        assert!(RateCurve { optimal_utilization_bps: 0, ..CURVE }.validate().is_err());
+    }
+
+    #[test]
+    fn borrow_index_grows_linearly_between_updates() {
+        let index = grow_index(WAD, 1_000, YEAR).unwrap();
+        assert_eq!(index, WAD + WAD / 10);
+        assert_eq!(grow_index(index, 1_000, 0).unwrap(), index);
+    }
+
+    #[test]
+    fn overflow_is_an_error() {
+        assert!(continuous_interest(u64::MAX, 10_000, 100 * YEAR).is_err());
+        assert!(InterestModel::Compound { period: 0 }.validate().is_err());
+    }
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::interest::BPS_DENOMINATOR;
+use crate::safe_math::{to_u64, SafeMath};
+use crate::VaultError;
+
+/// Oldest a price feed update may be before prices read from it are refused
+pub const MAX_PRICE_AGE: i64 = 60; // seconds
+
+/// Risk parameters for lending out of a vault, all in basis points of collateral value
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct BorrowConfig {
+    pub max_ltv_bps: u16, // most debt a borrow may leave; 0 disables borrowing
+    pub liquidation_threshold_bps: u16, // debt above this can be liquidated
+    pub liquidation_bonus_bps: u16, // extra collateral a liquidator seizes over what they repay
+    pub close_factor_bps: u16, // share of a position's debt one liquidation may repay
+}
+
+impl BorrowConfig {
+    pub const LEN: usize = 2 + 2 + 2 + 2;
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.max_ltv_bps <= self.liquidation_threshold_bps
+                && (self.liquidation_threshold_bps as u128) < BPS_DENOMINATOR
+                && self.close_factor_bps as u128 <= BPS_DENOMINATOR,
+            VaultError::InvalidBorrowConfig
+        );
+        // Seizing the bonus must leave a liquidated position healthier, not worse off
+        require!(
+            (self.liquidation_threshold_bps as u128)
+                .safe_mul(BPS_DENOMINATOR.safe_add(self.liquidation_bonus_bps as u128)?)?
+                < BPS_DENOMINATOR.safe_mul(BPS_DENOMINATOR)?,
+            VaultError::InvalidBorrowConfig
+        );
+        if self.is_enabled() {
+            require!(self.close_factor_bps > 0, VaultError::InvalidBorrowConfig);
+        }
+        Ok(())
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.max_ltv_bps > 0
+    }
+}
+
+/// `amount` of a token priced at `from_price`, in units of a token priced at `to_price`.
+/// Prices are quote units per whole token, so decimals are scaled out on both sides.
+pub fn convert_at_prices(
+    amount: u64,
+    from_price: u64,
+    from_decimals: u8,
+    to_price: u64,
+    to_decimals: u8,
+) -> Result<u64> {
+    let numerator = (amount as u128)
+        .safe_mul(from_price as u128)?
+        .safe_mul(10u128.checked_pow(to_decimals as u32).ok_or(VaultError::MathOverflow)?)?;
+    let denominator = (to_price as u128)
+        .safe_mul(10u128.checked_pow(from_decimals as u32).ok_or(VaultError::MathOverflow)?)?;
+    to_u64(numerator.safe_div(denominator)?)
+}
@@ -1,8 +1,51 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer, Mint};
+use anchor_lang::solana_program::sysvar::instructions::{
+    self as instructions_sysvar, load_current_index_checked, load_instruction_at_checked,
+};
+use anchor_lang::Discriminator;
+use anchor_spl::token_interface::{
+    self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+pub mod fees;
+pub mod interest;
+pub mod lending;
+pub mod outflow;
+pub mod safe_math;
+pub mod strategy;
+
+use fees::{bps_of, FeeConfig, FeeKind};
+use interest::{InterestModel, RateCurve, WAD};
+use lending::{BorrowConfig, MAX_PRICE_AGE};
+use outflow::OutflowLimit;
+use safe_math::{to_u64, SafeMath};
 
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 
+/// `Vault.paused_flags` bit that blocks new deposits
+pub const PAUSE_DEPOSITS: u8 = 1 << 0;
+/// `Vault.paused_flags` bit that blocks withdrawals
+pub const PAUSE_WITHDRAWALS: u8 = 1 << 1;
+pub const PAUSE_ALL: u8 = PAUSE_DEPOSITS | PAUSE_WITHDRAWALS;
+
+/// Number of term slots in `Vault.lock_terms`
+pub const MAX_LOCK_TERMS: usize = 4;
+
+/// Number of stream slots in `Vault.reward_streams`
+pub const MAX_REWARD_STREAMS: usize = 4;
+
+/// Trailing PDA seed for the `vault`, `vault-token` and `vault-reserve` derivations.
+///
+/// Vault 0 contributes an empty seed, so it derives to the same addresses as the original
+/// one-vault-per-mint seeds and existing vaults keep working as vault 0.
+pub fn vault_id_seed(vault_id: u64) -> Vec<u8> {
+    if vault_id == 0 {
+        Vec::new()
+    } else {
+        vault_id.to_le_bytes().to_vec()
+    }
+}
+
 #[program]
 pub mod defi_vault {
     use super::*;
@@ -10,20 +53,65 @@ pub mod defi_vault {
     /// Initialize a new vault for a specific SPL token
     pub fn initialize_vault(
         ctx: Context<InitializeVault>,
+        vault_id: u64, // distinguishes vaults that share a mint; 0 is the mint's original vault
         interest_rate: u64, // Interest rate in basis points (e.g., 500 = 5%)
         min_deposit: u64,
+        interest_model: InterestModel,
     ) -> Result<()> {
+        interest_model.validate()?;
+        
         let vault = &mut ctx.accounts.vault;
         vault.authority = ctx.accounts.authority.key();
+        vault.pending_authority = None;
         vault.token_mint = ctx.accounts.token_mint.key();
+        vault.vault_id = vault_id;
         vault.token_vault = ctx.accounts.token_vault.key();
+        vault.token_program = ctx.accounts.token_program.key();
+        vault.reward_reserve = ctx.accounts.reward_reserve.key();
+        vault.treasury = ctx.accounts.treasury.key();
         vault.interest_rate = interest_rate;
+        vault.interest_model = interest_model;
+        vault.rate_curve = None;
+        vault.total_borrowed = 0;
+        vault.total_collateral_shares = 0;
+        vault.borrow_rate = interest_rate;
+        vault.borrow_index = interest::WAD;
+        vault.borrow_config = BorrowConfig::default();
+        vault.price_feed = None;
+        vault.reward_streams = [None; MAX_REWARD_STREAMS];
+        vault.flash_loan_amount = 0;
+        vault.flash_loan_fee = 0;
+        vault.total_allocated = 0;
+        vault.loss_threshold_bps = None;
         vault.min_deposit = min_deposit;
+        vault.fees = FeeConfig::default();
+        vault.max_total_deposits = None;
+        vault.max_per_user = None;
+        vault.withdrawal_cooldown = 0;
+        vault.guardian = None;
+        vault.outflow_limit = OutflowLimit::default();
+        vault.outflow_window_start = 0;
+        vault.outflow_window_amount = 0;
+        vault.outflow_window_tvl = 0;
+        vault.paused_flags = 0;
         vault.total_deposited = 0;
+        vault.total_shares = 0;
+        vault.total_accrued_interest = 0;
+        vault.total_locked = 0;
+        vault.total_locked_interest = 0;
+        vault.accrued_vault_fees = 0;
+        vault.accrued_reserve_fees = 0;
+        vault.pending_principal = 0;
+        vault.pending_interest = 0;
+        vault.lock_terms = [LockTerm::default(); MAX_LOCK_TERMS];
+        vault.latest_unlock_time = 0;
+        vault.sunset_deadline = None;
         vault.bump = ctx.bumps.vault;
         vault.created_at = Clock::get()?.unix_timestamp;
+        vault.last_accrual_time = vault.created_at;
+        vault.borrow_index_updated_at = vault.created_at;
         
-        msg!("Vault initialized with interest rate: {}bps", interest_rate);
+        msg!("Vault {} initialized with interest rate: {}bps", vault_id, interest_rate);
         Ok(())
     }
 
@@ -31,318 +119,4935 @@ pub mod defi_vault {
     pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
+        require!(!vault.is_paused(PAUSE_DEPOSITS), VaultError::Paused);
+        require!(vault.flash_loan_amount == 0, VaultError::FlashLoanActive);
+        require!(vault.sunset_deadline.is_none(), VaultError::VaultSunsetting);
         require!(amount >= vault.min_deposit, VaultError::InsufficientDepositAmount);
         
-        // Transfer tokens from user to vault using standard SPL token
-        let cpi_accounts = Transfer {
+        // Bring vault-wide interest up to date so new shares are priced fairly
+        let current_time = Clock::get()?.unix_timestamp;
+        accrue_vault(vault, current_time)?;
+        
+        // Transfer tokens from user to vault through whichever token program owns the mint
+        let balance_before = ctx.accounts.vault_token_account.amount;
+        let cpi_accounts = TransferChecked {
             from: ctx.accounts.user_token_account.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
             to: ctx.accounts.vault_token_account.to_account_info(),
             authority: ctx.accounts.user.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        token::transfer(cpi_ctx, amount)?;
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.token_mint.decimals)?;
+
+        // Credit what the vault actually received; transfer-fee mints withhold part of `amount`
+        ctx.accounts.vault_token_account.reload()?;
+        let net_amount = ctx.accounts.vault_token_account.amount.safe_sub(balance_before)?;
+        
+        // The entry fee stays in the vault token account until swept by `collect_fees`
+        let fee = bps_of(net_amount, vault.fees.entry_fee_bps)?;
+        let credited_amount = net_amount.safe_sub(fee)?;
+        
+        let shares = vault.convert_to_shares(credited_amount)?;
+        require!(shares > 0, VaultError::ZeroShares);
 
         // Update or create user position
         let user_position = &mut ctx.accounts.user_position;
-        let current_time = Clock::get()?.unix_timestamp;
+        user_position.bind(ctx.accounts.user.key(), vault.key())?;
+        settle_rewards(vault, ctx.remaining_accounts, user_position, current_time)?;
         
-        // Calculate accrued interest on existing deposit before adding new deposit
-        if user_position.deposited_amount > 0 {
-            let accrued_interest = calculate_interest(
-                user_position.deposited_amount,
-                vault.interest_rate,
-                current_time - user_position.last_update_time,
-            );
-            user_position.accrued_interest += accrued_interest;
-        }
+        vault.check_deposit_cap(credited_amount)?;
+        vault.check_user_cap(user_position, credited_amount)?;
         
-        user_position.owner = ctx.accounts.user.key();
-        user_position.vault = vault.key();
-        user_position.deposited_amount += amount;
+        user_position.shares = user_position.shares.safe_add(shares)?;
+        user_position.deposited_amount = user_position.deposited_amount.safe_add(credited_amount)?;
         user_position.last_update_time = current_time;
-        user_position.deposit_count += 1;
+        user_position.deposit_count = user_position.deposit_count.safe_add(1)?;
+        
+        vault.total_shares = vault.total_shares.safe_add(shares)?;
+        vault.total_deposited = vault.total_deposited.safe_add(credited_amount)?;
+        vault.accrued_vault_fees = vault.accrued_vault_fees.safe_add(fee)?;
+        vault.refresh_rate()?;
         
-        vault.total_deposited += amount;
+        if fee > 0 {
+            emit!(FeeChargedEvent {
+                vault: vault.key(),
+                user: Some(ctx.accounts.user.key()),
+                kind: FeeKind::Entry,
+                amount: fee,
+                timestamp: current_time,
+            });
+        }
         
         emit!(DepositEvent {
             user: ctx.accounts.user.key(),
             vault: vault.key(),
             amount,
+            net_amount,
+            fee_amount: fee,
+            shares,
             timestamp: current_time,
         });
         
-        msg!("Deposited {} tokens. Total deposited: {}", amount, user_position.deposited_amount);
+        msg!("Deposited {} tokens ({} after fees) for {} shares. Total shares: {}", amount, net_amount, shares, user_position.shares);
         Ok(())
     }
 
-    /// Withdraw tokens from the vault including accrued interest
-    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+    /// Deposit tokens into a time-locked sub-position using one of the vault's lock terms
+    pub fn deposit_locked(ctx: Context<DepositLocked>, amount: u64, term_id: u8) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
+        
+        require!(!vault.is_paused(PAUSE_DEPOSITS), VaultError::Paused);
+        require!(vault.flash_loan_amount == 0, VaultError::FlashLoanActive);
+        require!(vault.sunset_deadline.is_none(), VaultError::VaultSunsetting);
+        require!(amount >= vault.min_deposit, VaultError::InsufficientDepositAmount);
+        
+        let term = vault.lock_term(term_id)?;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        let balance_before = ctx.accounts.vault_token_account.amount;
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            to: ctx.accounts.vault_token_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.token_mint.decimals)?;
+        
+        ctx.accounts.vault_token_account.reload()?;
+        let net_amount = ctx.accounts.vault_token_account.amount.safe_sub(balance_before)?;
+        vault.check_deposit_cap(net_amount)?;
+        
         let user_position = &mut ctx.accounts.user_position;
+        user_position.bind(ctx.accounts.user.key(), vault.key())?;
+        vault.check_user_cap(user_position, net_amount)?;
+        
+        let lock_position = &mut ctx.accounts.lock_position;
+        lock_position.owner = ctx.accounts.user.key();
+        lock_position.vault = vault.key();
+        lock_position.lock_id = user_position.lock_count;
+        lock_position.term_id = term_id;
+        lock_position.amount = net_amount;
+        lock_position.interest_rate = term.interest_rate;
+        lock_position.early_withdrawal_penalty_bps = term.early_withdrawal_penalty_bps;
+        lock_position.start_time = current_time;
+        lock_position.unlock_time = current_time.safe_add(term.duration)?;
+        lock_position.bump = ctx.bumps.lock_position;
+        
+        user_position.lock_count = user_position.lock_count.safe_add(1)?;
+        user_position.open_locks = user_position.open_locks.safe_add(1)?;
+        user_position.locked_amount = user_position.locked_amount.safe_add(net_amount)?;
+        vault.total_locked = vault.total_locked.safe_add(net_amount)?;
+        vault.total_locked_interest = vault.total_locked_interest.safe_add(lock_position.promised_interest()?)?;
+        vault.latest_unlock_time = vault.latest_unlock_time.max(lock_position.unlock_time);
+        
+        emit!(LockedDepositEvent {
+            user: ctx.accounts.user.key(),
+            vault: vault.key(),
+            lock_id: lock_position.lock_id,
+            term_id,
+            amount,
+            net_amount,
+            interest_rate: term.interest_rate,
+            unlock_time: lock_position.unlock_time,
+            timestamp: current_time,
+        });
+        
+        msg!("Locked {} tokens until {} at {}bps", net_amount, lock_position.unlock_time, term.interest_rate);
+        Ok(())
+    }
+
+    /// Update the interest rate, minimum deposit and interest model of an existing vault.
+    /// While a rate curve is set, the curve keeps deciding the rate.
+    pub fn update_vault_params(
+        ctx: Context<UpdateVaultParams>,
+        interest_rate: u64, // Interest rate in basis points (e.g., 500 = 5%)
+        min_deposit: u64,
+        interest_model: InterestModel,
+    ) -> Result<()> {
+        interest_model.validate()?;
+        
+        let vault = &mut ctx.accounts.vault;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        // Checkpoint interest at the old rate so existing positions are not repriced retroactively
+        accrue_vault(vault, current_time)?;
+        
+        let old_interest_rate = vault.interest_rate;
+        let old_min_deposit = vault.min_deposit;
+        let old_interest_model = vault.interest_model;
+        vault.interest_rate = interest_rate;
+        vault.min_deposit = min_deposit;
+        vault.interest_model = interest_model;
+        vault.refresh_rate()?;
+        
+        emit!(VaultParamsUpdatedEvent {
+            vault: vault.key(),
+            authority: ctx.accounts.authority.key(),
+            old_interest_rate,
+            new_interest_rate: interest_rate,
+            old_min_deposit,
+            new_min_deposit: min_deposit,
+            old_interest_model,
+            new_interest_model: interest_model,
+            timestamp: current_time,
+        });
+        
+        msg!("Vault params updated. Interest rate: {}bps, min deposit: {}", interest_rate, min_deposit);
+        Ok(())
+    }
+
+    /// Set a utilization-based rate curve. `None` turns it off and keeps the rate it last
+    /// produced as the flat `interest_rate`, which `update_vault_params` can then change.
+    pub fn set_rate_curve(ctx: Context<SetRateCurve>, rate_curve: Option<RateCurve>) -> Result<()> {
+        if let Some(curve) = &rate_curve {
+            curve.validate()?;
+        }
+        
+        let vault = &mut ctx.accounts.vault;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        // Interest up to now is owed at the rate that applied before the change
+        accrue_vault(vault, current_time)?;
+        
+        vault.rate_curve = rate_curve;
+        vault.refresh_rate()?;
+        
+        emit!(RateCurveUpdatedEvent {
+            vault: vault.key(),
+            authority: ctx.accounts.authority.key(),
+            rate_curve,
+            interest_rate: vault.interest_rate,
+            timestamp: current_time,
+        });
+        
+        msg!("Rate curve updated. Current rate: {}bps", vault.interest_rate);
+        Ok(())
+    }
+
+    /// Update the vault's protocol fees
+    pub fn set_fees(ctx: Context<SetFees>, fees: FeeConfig) -> Result<()> {
+        fees.validate()?;
+        
+        let vault = &mut ctx.accounts.vault;
         let current_time = Clock::get()?.unix_timestamp;
         
-        // Calculate total available balance (principal + accrued interest)
-        let accrued_interest = calculate_interest(
-            user_position.deposited_amount,
-            vault.interest_rate,
-            current_time - user_position.last_update_time,
+        // Charge performance and management fees owed so far at the old rates
+        accrue_vault(vault, current_time)?;
+        
+        let old_fees = vault.fees;
+        vault.fees = fees;
+        
+        emit!(FeesUpdatedEvent {
+            vault: vault.key(),
+            authority: ctx.accounts.authority.key(),
+            old_fees,
+            new_fees: fees,
+            timestamp: current_time,
+        });
+        
+        msg!(
+            "Vault fees set. Entry: {}bps, exit: {}bps, performance: {}bps, management: {}bps, flash loan: {}bps",
+            fees.entry_fee_bps,
+            fees.exit_fee_bps,
+            fees.performance_fee_bps,
+            fees.management_fee_bps,
+            fees.flash_loan_fee_bps
         );
+        Ok(())
+    }
+
+    /// Set or lift the vault-wide and per-user deposit caps; `None` means uncapped
+    pub fn set_deposit_caps(
+        ctx: Context<SetDepositCaps>,
+        max_total_deposits: Option<u64>,
+        max_per_user: Option<u64>,
+    ) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.max_total_deposits = max_total_deposits;
+        vault.max_per_user = max_per_user;
         
-        let total_available = user_position.deposited_amount + user_position.accrued_interest + accrued_interest;
+        emit!(DepositCapsUpdatedEvent {
+            vault: vault.key(),
+            authority: ctx.accounts.authority.key(),
+            max_total_deposits,
+            max_per_user,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         
-        require!(amount <= total_available, VaultError::InsufficientBalance);
+        msg!("Deposit caps set. Total: {:?}, per user: {:?}", max_total_deposits, max_per_user);
+        Ok(())
+    }
+
+    /// Set the withdrawal cooldown in seconds; 0 switches the vault back to instant `withdraw`
+    pub fn set_withdrawal_cooldown(ctx: Context<SetWithdrawalCooldown>, withdrawal_cooldown: i64) -> Result<()> {
+        require!(withdrawal_cooldown >= 0, VaultError::InvalidWithdrawalCooldown);
+        
+        let vault = &mut ctx.accounts.vault;
+        let old_withdrawal_cooldown = vault.withdrawal_cooldown;
+        vault.withdrawal_cooldown = withdrawal_cooldown;
+        
+        emit!(WithdrawalCooldownUpdatedEvent {
+            vault: vault.key(),
+            authority: ctx.accounts.authority.key(),
+            old_withdrawal_cooldown,
+            new_withdrawal_cooldown: withdrawal_cooldown,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Withdrawal cooldown set to {}s", withdrawal_cooldown);
+        Ok(())
+    }
+
+    /// Set or remove the guardian, a key that may only tighten the vault's safety limits
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Option<Pubkey>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        let old_guardian = vault.guardian;
+        vault.guardian = guardian;
+        
+        emit!(GuardianUpdatedEvent {
+            vault: vault.key(),
+            authority: ctx.accounts.authority.key(),
+            old_guardian,
+            new_guardian: guardian,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Vault guardian set to: {:?}", guardian);
+        Ok(())
+    }
+
+    /// Set the outflow limit. The authority may set any limit; the guardian may only
+    /// replace it with a tighter one.
+    pub fn set_outflow_limit(ctx: Context<SetOutflowLimit>, outflow_limit: OutflowLimit) -> Result<()> {
+        outflow_limit.validate()?;
+        
+        let vault = &mut ctx.accounts.vault;
+        let signer = ctx.accounts.signer.key();
+        if signer != vault.authority {
+            require!(vault.guardian == Some(signer), VaultError::Unauthorized);
+            require!(
+                outflow_limit.is_tighter_than(&vault.outflow_limit),
+                VaultError::GuardianCannotLoosen
+            );
+        }
+        
+        let old_outflow_limit = vault.outflow_limit;
+        vault.outflow_limit = outflow_limit;
+        
+        emit!(OutflowLimitUpdatedEvent {
+            vault: vault.key(),
+            updated_by: signer,
+            old_outflow_limit,
+            new_outflow_limit: outflow_limit,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!(
+            "Outflow limit set: {:?} tokens, {:?}bps of TVL per {}s",
+            outflow_limit.max_amount,
+            outflow_limit.max_tvl_bps,
+            outflow_limit.window
+        );
+        Ok(())
+    }
+
+    /// Configure one of the vault's lock terms; a zero duration disables the slot
+    pub fn set_lock_term(
+        ctx: Context<SetLockTerm>,
+        term_id: u8,
+        duration: i64,
+        interest_rate: u64, // Interest rate in basis points (e.g., 500 = 5%)
+        early_withdrawal_penalty_bps: Option<u16>, // None refuses early withdrawal
+    ) -> Result<()> {
+        require!((term_id as usize) < MAX_LOCK_TERMS, VaultError::InvalidLockTerm);
+        require!(duration >= 0, VaultError::InvalidLockTerm);
+        if let Some(penalty_bps) = early_withdrawal_penalty_bps {
+            require!(penalty_bps as u128 <= interest::BPS_DENOMINATOR, VaultError::InvalidLockTerm);
+        }
+        
+        let term = LockTerm {
+            duration,
+            interest_rate,
+            early_withdrawal_penalty_bps,
+        };
+        let vault = &mut ctx.accounts.vault;
+        vault.lock_terms[term_id as usize] = term;
+        
+        emit!(LockTermUpdatedEvent {
+            vault: vault.key(),
+            term_id,
+            duration,
+            interest_rate,
+            early_withdrawal_penalty_bps,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Lock term {} set: {}s at {}bps", term_id, duration, interest_rate);
+        Ok(())
+    }
+
+    /// Pause or unpause deposits and withdrawals independently
+    pub fn set_pause(ctx: Context<SetPause>, paused_flags: u8) -> Result<()> {
+        require!(paused_flags & !PAUSE_ALL == 0, VaultError::InvalidPauseFlags);
+        
+        let vault = &mut ctx.accounts.vault;
+        vault.paused_flags = paused_flags;
+        
+        emit!(PauseUpdatedEvent {
+            vault: vault.key(),
+            authority: ctx.accounts.authority.key(),
+            paused_flags,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!(
+            "Vault pause flags set. Deposits paused: {}, withdrawals paused: {}",
+            vault.is_paused(PAUSE_DEPOSITS),
+            vault.is_paused(PAUSE_WITHDRAWALS)
+        );
+        Ok(())
+    }
+
+    /// Propose a new vault authority, which must accept before taking over
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        require!(new_authority != vault.authority, VaultError::InvalidAuthority);
+        
+        vault.pending_authority = Some(new_authority);
+        
+        emit!(AuthorityProposedEvent {
+            vault: vault.key(),
+            authority: vault.authority,
+            pending_authority: new_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Proposed new vault authority: {}", new_authority);
+        Ok(())
+    }
+
+    /// Accept a pending authority transfer; must be signed by the proposed authority
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        require!(
+            vault.pending_authority == Some(ctx.accounts.pending_authority.key()),
+            VaultError::NotPendingAuthority
+        );
+        
+        let previous_authority = vault.authority;
+        vault.authority = ctx.accounts.pending_authority.key();
+        vault.pending_authority = None;
+        
+        emit!(AuthorityAcceptedEvent {
+            vault: vault.key(),
+            previous_authority,
+            new_authority: vault.authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Vault authority transferred to: {}", vault.authority);
+        Ok(())
+    }
+
+    /// Cancel a pending authority transfer
+    pub fn cancel_authority_transfer(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        let pending_authority = vault
+            .pending_authority
+            .take()
+            .ok_or(VaultError::NoPendingAuthority)?;
+        
+        emit!(AuthorityTransferCancelledEvent {
+            vault: vault.key(),
+            authority: vault.authority,
+            cancelled_authority: pending_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Cancelled authority transfer to: {}", pending_authority);
+        Ok(())
+    }
+
+    /// Top up the reward reserve that pays out accrued interest
+    pub fn fund_reserve(ctx: Context<FundReserve>, amount: u64) -> Result<()> {
+        require!(amount > 0, VaultError::InvalidAmount);
+
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.authority_token_account.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            to: ctx.accounts.reward_reserve.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.token_mint.decimals)?;
+
+        ctx.accounts.reward_reserve.reload()?;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        emit!(ReserveFundedEvent {
+            vault: ctx.accounts.vault.key(),
+            funder: ctx.accounts.authority.key(),
+            amount,
+            reserve_balance: ctx.accounts.reward_reserve.amount,
+            timestamp: current_time,
+        });
+
+        msg!("Funded reserve with {} tokens. Reserve balance: {}", amount, ctx.accounts.reward_reserve.amount);
+        Ok(())
+    }
+
+    /// Sweep accumulated protocol fees to the vault treasury.
+    ///
+    /// Anyone may call this since fees can only go to `vault.treasury`. Fees owed from the
+    /// reward reserve are swept only as far as its balance allows after pending withdrawals;
+    /// the rest stays owed.
+    pub fn collect_fees(ctx: Context<CollectFees>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(vault.flash_loan_amount == 0, VaultError::FlashLoanActive);
+        
+        accrue_vault(vault, current_time)?;
+        
+        let vault_fees = vault.accrued_vault_fees;
+        let reserve_fees = vault
+            .accrued_reserve_fees
+            .min(vault.available_reserve(ctx.accounts.reward_reserve.amount));
         
-        // Transfer tokens from vault to user using standard SPL token
+        let id_seed = vault.id_seed();
         let seeds = &[
             b"vault",
             vault.token_mint.as_ref(),
+            id_seed.as_ref(),
             &[vault.bump],
         ];
         let signer = &[&seeds[..]];
         
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.vault_token_account.to_account_info(),
-            to: ctx.accounts.user_token_account.to_account_info(),
-            authority: vault.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        token::transfer(cpi_ctx, amount)?;
+        if vault_fees > 0 {
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.vault_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+                authority: vault.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token_interface::transfer_checked(cpi_ctx, vault_fees, ctx.accounts.token_mint.decimals)?;
+        }
+        
+        if reserve_fees > 0 {
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.reward_reserve.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+                authority: vault.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token_interface::transfer_checked(cpi_ctx, reserve_fees, ctx.accounts.token_mint.decimals)?;
+        }
+        
+        vault.accrued_vault_fees = 0;
+        vault.accrued_reserve_fees = vault.accrued_reserve_fees.safe_sub(reserve_fees)?;
+        
+        let amount = vault_fees.safe_add(reserve_fees)?;
+        
+        emit!(FeesCollectedEvent {
+            vault: vault.key(),
+            treasury: vault.treasury,
+            amount,
+            outstanding: vault.accrued_reserve_fees,
+            timestamp: current_time,
+        });
+        
+        msg!("Collected {} tokens of fees. Outstanding: {}", amount, vault.accrued_reserve_fees);
+        Ok(())
+    }
 
-        // Update user position
-        let mut remaining_withdrawal = amount;
+    /// Withdraw tokens from the vault including accrued interest
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        let user_position = &mut ctx.accounts.user_position;
+        let current_time = Clock::get()?.unix_timestamp;
         
-        // First, withdraw from accrued interest
-        let total_accrued = user_position.accrued_interest + accrued_interest;
-        if remaining_withdrawal <= total_accrued {
-            user_position.accrued_interest = total_accrued - remaining_withdrawal;
-            remaining_withdrawal = 0;
-        } else {
-            remaining_withdrawal -= total_accrued;
-            user_position.accrued_interest = 0;
-            // Withdraw from principal
-            user_position.deposited_amount -= remaining_withdrawal;
-            vault.total_deposited -= remaining_withdrawal;
+        require!(!vault.is_paused(PAUSE_WITHDRAWALS), VaultError::Paused);
+        require!(vault.flash_loan_amount == 0, VaultError::FlashLoanActive);
+        require!(vault.withdrawal_cooldown == 0, VaultError::WithdrawalCooldownActive);
+        
+        accrue_vault(vault, current_time)?;
+        vault.record_outflow(amount, current_time)?;
+        settle_rewards(vault, ctx.remaining_accounts, user_position, current_time)?;
+        
+        let redemption = vault.redeem(user_position, amount)?;
+        vault.refresh_rate()?;
+        
+        require!(
+            vault.available_reserve(ctx.accounts.reward_reserve.amount) >= redemption.interest_transfer,
+            VaultError::InsufficientReserve
+        );
+        
+        let id_seed = vault.id_seed();
+        let seeds = &[
+            b"vault",
+            vault.token_mint.as_ref(),
+            id_seed.as_ref(),
+            &[vault.bump],
+        ];
+        let signer = &[&seeds[..]];
+        
+        // Transfer-fee mints withhold part of each payout at the destination, so the
+        // position is debited the full amount and the user receives `net_amount`
+        let balance_before = ctx.accounts.user_token_account.amount;
+        
+        if redemption.interest_transfer > 0 {
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.reward_reserve.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: vault.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token_interface::transfer_checked(cpi_ctx, redemption.interest_transfer, ctx.accounts.token_mint.decimals)?;
+        }
+        
+        if redemption.principal_transfer > 0 {
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.vault_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: vault.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token_interface::transfer_checked(cpi_ctx, redemption.principal_transfer, ctx.accounts.token_mint.decimals)?;
         }
         
+        ctx.accounts.user_token_account.reload()?;
+        let net_amount = ctx.accounts.user_token_account.amount.safe_sub(balance_before)?;
+        
         user_position.last_update_time = current_time;
-        user_position.withdraw_count += 1;
+        user_position.withdraw_count = user_position.withdraw_count.safe_add(1)?;
+        
+        if redemption.fee > 0 {
+            emit!(FeeChargedEvent {
+                vault: vault.key(),
+                user: Some(ctx.accounts.user.key()),
+                kind: FeeKind::Exit,
+                amount: redemption.fee,
+                timestamp: current_time,
+            });
+        }
         
         emit!(WithdrawEvent {
             user: ctx.accounts.user.key(),
             vault: vault.key(),
             amount,
+            net_amount,
+            fee_amount: redemption.fee,
+            shares: redemption.shares,
+            interest_amount: redemption.interest_amount,
+            principal_amount: redemption.principal_amount,
             timestamp: current_time,
         });
         
-        msg!("Withdrew {} tokens. Remaining deposited: {}", amount, user_position.deposited_amount);
+        msg!("Withdrew {} tokens ({} after fees) for {} shares. Remaining shares: {}", amount, net_amount, redemption.shares, user_position.shares);
         Ok(())
     }
 
-    /// Get user's current balance including accrued interest
-    pub fn get_user_balance(ctx: Context<GetUserBalance>) -> Result<UserBalanceInfo> {
-        let user_position = &ctx.accounts.user_position;
-        let vault = &ctx.accounts.vault;
+    /// Queue a withdrawal on a vault with a cooldown. The shares are burned now, so the
+    /// amount stops earning interest, and `claim_withdraw` pays it out once the cooldown ends.
+    ///
+    /// Further requests add to the same pending withdrawal and restart its cooldown.
+    pub fn request_withdraw(ctx: Context<RequestWithdraw>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        let user_position = &mut ctx.accounts.user_position;
         let current_time = Clock::get()?.unix_timestamp;
         
-        let accrued_interest = calculate_interest(
-            user_position.deposited_amount,
-            vault.interest_rate,
-            current_time - user_position.last_update_time,
-        );
+        require!(!vault.is_paused(PAUSE_WITHDRAWALS), VaultError::Paused);
+        require!(vault.withdrawal_cooldown > 0, VaultError::WithdrawalCooldownDisabled);
+        require!(vault.flash_loan_amount == 0, VaultError::FlashLoanActive);
         
-        let total_balance = user_position.deposited_amount + user_position.accrued_interest + accrued_interest;
+        accrue_vault(vault, current_time)?;
+        settle_rewards(vault, ctx.remaining_accounts, user_position, current_time)?;
         
-        Ok(UserBalanceInfo {
-            deposited_amount: user_position.deposited_amount,
-            accrued_interest: user_position.accrued_interest + accrued_interest,
-            total_balance,
-            last_update_time: user_position.last_update_time,
-        })
-    }
-}
-
-// Helper function to calculate interest
-fn calculate_interest(principal: u64, interest_rate_bps: u64, time_elapsed: i64) -> u64 {
-    if principal == 0 || time_elapsed <= 0 {
-        return 0;
-    }
-    
-    // Simple interest calculation: (principal * rate * time) / (10000 * seconds_per_year)
-    // Rate is in basis points (1 basis point = 0.01%)
-    let seconds_per_year = 365 * 24 * 60 * 60;
-    let interest = (principal as u128)
-        .checked_mul(interest_rate_bps as u128)
-        .unwrap()
-        .checked_mul(time_elapsed as u128)
-        .unwrap()
-        .checked_div(10000u128)
-        .unwrap()
-        .checked_div(seconds_per_year as u128)
-        .unwrap();
+        let redemption = vault.redeem(user_position, amount)?;
+        vault.refresh_rate()?;
+        
+        let pending = &mut ctx.accounts.pending_withdrawal;
+        if pending.owner == Pubkey::default() {
+            pending.owner = ctx.accounts.user.key();
+            pending.vault = vault.key();
+            pending.bump = ctx.bumps.pending_withdrawal;
+        }
+        pending.interest_amount = pending.interest_amount.safe_add(redemption.interest_transfer)?;
+        pending.principal_amount = pending.principal_amount.safe_add(redemption.principal_transfer)?;
+        pending.claimable_at = current_time.safe_add(vault.withdrawal_cooldown)?;
+        
+        vault.pending_interest = vault.pending_interest.safe_add(redemption.interest_transfer)?;
+        vault.pending_principal = vault.pending_principal.safe_add(redemption.principal_transfer)?;
+        
+        user_position.last_update_time = current_time;
+        user_position.withdraw_count = user_position.withdraw_count.safe_add(1)?;
+        
+        if redemption.fee > 0 {
+            emit!(FeeChargedEvent {
+                vault: vault.key(),
+                user: Some(ctx.accounts.user.key()),
+                kind: FeeKind::Exit,
+                amount: redemption.fee,
+                timestamp: current_time,
+            });
+        }
+        
+        emit!(WithdrawRequestedEvent {
+            user: ctx.accounts.user.key(),
+            vault: vault.key(),
+            amount,
+            fee_amount: redemption.fee,
+            shares: redemption.shares,
+            interest_amount: redemption.interest_transfer,
+            principal_amount: redemption.principal_transfer,
+            claimable_at: pending.claimable_at,
+            timestamp: current_time,
+        });
+        
+        msg!("Requested withdrawal of {} tokens, claimable at {}", amount, pending.claimable_at);
+        Ok(())
+    }
+
+    /// Pay out a pending withdrawal once its cooldown has passed and close the record
+    pub fn claim_withdraw(ctx: Context<ClaimWithdraw>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        let pending = &ctx.accounts.pending_withdrawal;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(!vault.is_paused(PAUSE_WITHDRAWALS), VaultError::Paused);
+        require!(vault.flash_loan_amount == 0, VaultError::FlashLoanActive);
+        require!(current_time >= pending.claimable_at, VaultError::WithdrawalNotClaimable);
+        vault.record_outflow(
+            pending.interest_amount.safe_add(pending.principal_amount)?,
+            current_time,
+        )?;
+        require!(
+            ctx.accounts.reward_reserve.amount >= pending.interest_amount,
+            VaultError::InsufficientReserve
+        );
+        
+        let id_seed = vault.id_seed();
+        let seeds = &[
+            b"vault",
+            vault.token_mint.as_ref(),
+            id_seed.as_ref(),
+            &[vault.bump],
+        ];
+        let signer = &[&seeds[..]];
+        
+        let balance_before = ctx.accounts.user_token_account.amount;
+        
+        if pending.interest_amount > 0 {
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.reward_reserve.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: vault.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token_interface::transfer_checked(cpi_ctx, pending.interest_amount, ctx.accounts.token_mint.decimals)?;
+        }
+        
+        if pending.principal_amount > 0 {
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.vault_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: vault.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token_interface::transfer_checked(cpi_ctx, pending.principal_amount, ctx.accounts.token_mint.decimals)?;
+        }
+        
+        ctx.accounts.user_token_account.reload()?;
+        let net_amount = ctx.accounts.user_token_account.amount.safe_sub(balance_before)?;
+        
+        vault.pending_interest = vault.pending_interest.safe_sub(pending.interest_amount)?;
+        vault.pending_principal = vault.pending_principal.safe_sub(pending.principal_amount)?;
+        
+        emit!(WithdrawClaimedEvent {
+            user: ctx.accounts.user.key(),
+            vault: vault.key(),
+            interest_amount: pending.interest_amount,
+            principal_amount: pending.principal_amount,
+            net_amount,
+            timestamp: current_time,
+        });
+        
+        msg!("Claimed withdrawal of {} tokens", net_amount);
+        Ok(())
+    }
+
+    /// Withdraw a lock sub-position in full and close it.
+    ///
+    /// At maturity this pays principal plus the lock's interest. Before maturity it is
+    /// refused unless the term allows early exit, in which case interest is forfeited
+    /// and the term's penalty on principal goes to the vault treasury.
+    pub fn withdraw_locked(ctx: Context<WithdrawLocked>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        let lock_position = &ctx.accounts.lock_position;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(!vault.is_paused(PAUSE_WITHDRAWALS), VaultError::Paused);
+        require!(vault.flash_loan_amount == 0, VaultError::FlashLoanActive);
+        
+        let matured = current_time >= lock_position.unlock_time;
+        let principal = lock_position.amount;
+        // The lock's interest was set aside when it opened; release it, paid or forfeited
+        let promised_interest = lock_position.promised_interest()?;
+        vault.total_locked_interest = vault.total_locked_interest.safe_sub(promised_interest)?;
+        let (interest_payout, penalty) = if matured {
+            (promised_interest, 0)
+        } else {
+            let penalty_bps = lock_position
+                .early_withdrawal_penalty_bps
+                .ok_or(VaultError::LockNotMatured)?;
+            (0, bps_of(principal, penalty_bps)?)
+        };
+        let principal_payout = principal.safe_sub(penalty)?;
+        vault.record_outflow(interest_payout.safe_add(principal_payout)?, current_time)?;
+        
+        require!(
+            vault.available_reserve(ctx.accounts.reward_reserve.amount) >= interest_payout,
+            VaultError::InsufficientReserve
+        );
+        
+        let id_seed = vault.id_seed();
+        let seeds = &[
+            b"vault",
+            vault.token_mint.as_ref(),
+            id_seed.as_ref(),
+            &[vault.bump],
+        ];
+        let signer = &[&seeds[..]];
+        
+        let balance_before = ctx.accounts.user_token_account.amount;
+        
+        if interest_payout > 0 {
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.reward_reserve.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: vault.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token_interface::transfer_checked(cpi_ctx, interest_payout, ctx.accounts.token_mint.decimals)?;
+        }
+        
+        if principal_payout > 0 {
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.vault_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: vault.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token_interface::transfer_checked(cpi_ctx, principal_payout, ctx.accounts.token_mint.decimals)?;
+        }
+        
+        if penalty > 0 {
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.vault_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+                authority: vault.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token_interface::transfer_checked(cpi_ctx, penalty, ctx.accounts.token_mint.decimals)?;
+        }
+        
+        ctx.accounts.user_token_account.reload()?;
+        let net_amount = ctx.accounts.user_token_account.amount.safe_sub(balance_before)?;
+        
+        vault.total_locked = vault.total_locked.safe_sub(principal)?;
+        
+        let user_position = &mut ctx.accounts.user_position;
+        user_position.open_locks = user_position.open_locks.safe_sub(1)?;
+        user_position.locked_amount = user_position.locked_amount.safe_sub(principal)?;
+        
+        emit!(LockedWithdrawEvent {
+            user: ctx.accounts.user.key(),
+            vault: vault.key(),
+            lock_id: lock_position.lock_id,
+            principal_amount: principal_payout,
+            interest_amount: interest_payout,
+            penalty_amount: penalty,
+            net_amount,
+            early: !matured,
+            timestamp: current_time,
+        });
+        
+        msg!(
+            "Withdrew lock {}: {} principal, {} interest, {} penalty",
+            lock_position.lock_id,
+            principal_payout,
+            interest_payout,
+            penalty
+        );
+        Ok(())
+    }
+
+    /// Close an emptied user position and return its rent to the owner.
+    ///
+    /// Shares worth nothing at the current exchange rate are rounding dust and are burned
+    /// into the vault first. Open lock sub-positions must be withdrawn beforehand. The
+    /// vault's reward streams are settled before the dust is burned, so anything the dust
+    /// earned must be claimed too.
+    pub fn close_position(ctx: Context<ClosePosition>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        let user_position = &mut ctx.accounts.user_position;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        accrue_vault(vault, current_time)?;
+        settle_rewards(vault, ctx.remaining_accounts, user_position, current_time)?;
+        
+        require!(user_position.open_locks == 0, VaultError::PositionNotEmpty);
+        require!(
+            user_position.pending_rewards.iter().all(|&pending| pending == 0),
+            VaultError::PositionNotEmpty
+        );
+        require!(
+            vault.convert_to_assets(user_position.shares)? == 0,
+            VaultError::PositionNotEmpty
+        );
+        
+        let dust_shares = user_position.shares;
+        vault.total_shares = vault.total_shares.safe_sub(dust_shares)?;
+        
+        emit!(PositionClosedEvent {
+            user: ctx.accounts.user.key(),
+            vault: vault.key(),
+            dust_shares,
+            timestamp: current_time,
+        });
+        
+        msg!("Closed position for {}. Burned {} dust shares", ctx.accounts.user.key(), dust_shares);
+        Ok(())
+    }
+
+    /// Borrow from this vault against shares held in another vault.
+    ///
+    /// `collateral_shares` move out of the caller's position in the collateral vault into
+    /// the borrow position, where they keep earning but cannot be withdrawn. The debt may
+    /// not exceed the collateral's value times the borrow vault's `max_ltv_bps`. Collateral
+    /// in another mint is valued through both vaults' price feeds.
+    pub fn borrow(ctx: Context<Borrow>, collateral_shares: u64, amount: u64) -> Result<()> {
+        let borrow_vault = &mut ctx.accounts.borrow_vault;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(borrow_vault.borrow_config.is_enabled(), VaultError::BorrowingDisabled);
+        require!(!borrow_vault.is_paused(PAUSE_WITHDRAWALS), VaultError::Paused);
+        require!(borrow_vault.sunset_deadline.is_none(), VaultError::VaultSunsetting);
+        // A closing collateral vault would take the escrowed shares with it
+        require!(ctx.accounts.collateral_vault.sunset_deadline.is_none(), VaultError::VaultSunsetting);
+        require!(borrow_vault.flash_loan_amount == 0, VaultError::FlashLoanActive);
+        
+        accrue_vault(borrow_vault, current_time)?;
+        require!(amount <= borrow_vault.available_liquidity(), VaultError::InsufficientLiquidity);
+        borrow_vault.record_outflow(amount, current_time)?;
+        
+        // Move the collateral shares, and their share of the cost basis, out of the position
+        let user_position = &mut ctx.accounts.user_position;
+        settle_rewards(
+            &ctx.accounts.collateral_vault,
+            ctx.remaining_accounts,
+            user_position,
+            current_time,
+        )?;
+        require!(collateral_shares <= user_position.shares, VaultError::InsufficientBalance);
+        let collateral_basis = to_u64(
+            (user_position.deposited_amount as u128)
+                .safe_mul(collateral_shares as u128)?
+                .checked_div(user_position.shares as u128)
+                .unwrap_or(0),
+        )?;
+        user_position.shares = user_position.shares.safe_sub(collateral_shares)?;
+        user_position.deposited_amount = user_position.deposited_amount.safe_sub(collateral_basis)?;
+        
+        let borrow_position = &mut ctx.accounts.borrow_position;
+        if borrow_position.owner == Pubkey::default() {
+            borrow_position.owner = ctx.accounts.user.key();
+            borrow_position.borrow_vault = borrow_vault.key();
+            borrow_position.collateral_vault = ctx.accounts.collateral_vault.key();
+            borrow_position.borrow_index = borrow_vault.borrow_index;
+            borrow_position.bump = ctx.bumps.borrow_position;
+        }
+        borrow_position.accrue(borrow_vault.borrow_index)?;
+        borrow_position.collateral_shares = borrow_position.collateral_shares.safe_add(collateral_shares)?;
+        borrow_position.collateral_basis = borrow_position.collateral_basis.safe_add(collateral_basis)?;
+        borrow_position.debt = borrow_position.debt.safe_add(amount)?;
+        borrow_position.principal = borrow_position.principal.safe_add(amount)?;
+        
+        let collateral_value = collateral_value(
+            &ctx.accounts.collateral_vault,
+            borrow_vault,
+            borrow_position.collateral_shares,
+            ctx.accounts.collateral_price_feed.as_deref(),
+            ctx.accounts.borrow_price_feed.as_deref(),
+            current_time,
+        )?;
+        borrow_position.check_ltv(collateral_value, borrow_vault.borrow_config.max_ltv_bps)?;
+        
+        let collateral_vault = &mut ctx.accounts.collateral_vault;
+        collateral_vault.total_collateral_shares = collateral_vault.total_collateral_shares.safe_add(collateral_shares)?;
+        borrow_vault.total_borrowed = borrow_vault.total_borrowed.safe_add(amount)?;
+        borrow_vault.refresh_rate()?;
+        
+        let id_seed = borrow_vault.id_seed();
+        let seeds = &[
+            b"vault",
+            borrow_vault.token_mint.as_ref(),
+            id_seed.as_ref(),
+            &[borrow_vault.bump],
+        ];
+        let signer = &[&seeds[..]];
+        
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_token_account.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: borrow_vault.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.token_mint.decimals)?;
+        
+        emit!(BorrowEvent {
+            user: ctx.accounts.user.key(),
+            borrow_vault: borrow_vault.key(),
+            collateral_vault: borrow_position.collateral_vault,
+            amount,
+            collateral_shares,
+            debt: borrow_position.debt,
+            timestamp: current_time,
+        });
+        
+        msg!("Borrowed {} tokens. Debt: {}, collateral shares: {}", amount, borrow_position.debt, borrow_position.collateral_shares);
+        Ok(())
+    }
+
+    /// Repay up to `amount` of a borrow position's debt, interest first.
+    ///
+    /// Repaid interest goes to the borrow vault's reward reserve, where it funds depositors'
+    /// interest; repaid principal goes back to the vault token account.
+    pub fn repay(ctx: Context<Repay>, amount: u64) -> Result<()> {
+        let borrow_vault = &mut ctx.accounts.borrow_vault;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        accrue_vault(borrow_vault, current_time)?;
+        
+        let borrow_position = &mut ctx.accounts.borrow_position;
+        borrow_position.accrue(borrow_vault.borrow_index)?;
+        
+        let amount = amount.min(borrow_position.debt);
+        require!(amount > 0, VaultError::InvalidAmount);
+        
+        let (interest_repaid, principal_repaid) = repay_debt(
+            borrow_vault,
+            borrow_position,
+            amount,
+            &ctx.accounts.user,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.token_mint,
+            &mut ctx.accounts.vault_token_account,
+            &mut ctx.accounts.reward_reserve,
+            &ctx.accounts.token_program,
+        )?;
+        borrow_vault.refresh_rate()?;
+        
+        emit!(RepayEvent {
+            user: ctx.accounts.user.key(),
+            borrow_vault: borrow_vault.key(),
+            interest_amount: interest_repaid,
+            principal_amount: principal_repaid,
+            debt: borrow_position.debt,
+            timestamp: current_time,
+        });
+        
+        msg!("Repaid {} interest and {} principal. Remaining debt: {}", interest_repaid, principal_repaid, borrow_position.debt);
+        Ok(())
+    }
+
+    /// Move collateral shares back into the owner's position in the collateral vault,
+    /// as long as the remaining debt stays within the borrow vault's LTV
+    pub fn release_collateral(ctx: Context<ReleaseCollateral>, shares: u64) -> Result<()> {
+        let borrow_vault = &mut ctx.accounts.borrow_vault;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        accrue_vault(borrow_vault, current_time)?;
+        
+        let borrow_position = &mut ctx.accounts.borrow_position;
+        borrow_position.accrue(borrow_vault.borrow_index)?;
+        require!(shares > 0, VaultError::InvalidAmount);
+        require!(shares <= borrow_position.collateral_shares, VaultError::InsufficientBalance);
+        
+        let basis = to_u64(
+            (borrow_position.collateral_basis as u128)
+                .safe_mul(shares as u128)?
+                .safe_div(borrow_position.collateral_shares as u128)?,
+        )?;
+        borrow_position.collateral_shares = borrow_position.collateral_shares.safe_sub(shares)?;
+        borrow_position.collateral_basis = borrow_position.collateral_basis.safe_sub(basis)?;
+        
+        let collateral_value = collateral_value(
+            &ctx.accounts.collateral_vault,
+            borrow_vault,
+            borrow_position.collateral_shares,
+            ctx.accounts.collateral_price_feed.as_deref(),
+            ctx.accounts.borrow_price_feed.as_deref(),
+            current_time,
+        )?;
+        borrow_position.check_ltv(collateral_value, borrow_vault.borrow_config.max_ltv_bps)?;
+        
+        let user_position = &mut ctx.accounts.user_position;
+        user_position.bind(ctx.accounts.user.key(), ctx.accounts.collateral_vault.key())?;
+        settle_rewards(
+            &ctx.accounts.collateral_vault,
+            ctx.remaining_accounts,
+            user_position,
+            current_time,
+        )?;
+        user_position.shares = user_position.shares.safe_add(shares)?;
+        user_position.deposited_amount = user_position.deposited_amount.safe_add(basis)?;
+        user_position.last_update_time = current_time;
+        
+        let collateral_vault = &mut ctx.accounts.collateral_vault;
+        collateral_vault.total_collateral_shares = collateral_vault.total_collateral_shares.safe_sub(shares)?;
+        
+        emit!(CollateralReleasedEvent {
+            user: ctx.accounts.user.key(),
+            borrow_vault: borrow_vault.key(),
+            collateral_vault: borrow_position.collateral_vault,
+            shares,
+            debt: borrow_position.debt,
+            timestamp: current_time,
+        });
+        
+        msg!("Released {} collateral shares. Remaining: {}", shares, borrow_position.collateral_shares);
+        Ok(())
+    }
+
+    /// Liquidate part of an unhealthy borrow position.
+    ///
+    /// Once a position's debt exceeds the liquidation threshold of its collateral value, anyone
+    /// may repay up to the close factor of that debt and take the borrower's collateral shares
+    /// worth the repaid amount plus the liquidation bonus into their own position.
+    pub fn liquidate(ctx: Context<Liquidate>, repay_amount: u64) -> Result<()> {
+        let borrow_vault = &mut ctx.accounts.borrow_vault;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        accrue_vault(borrow_vault, current_time)?;
+        let config = borrow_vault.borrow_config;
+        
+        let borrow_position = &mut ctx.accounts.borrow_position;
+        borrow_position.accrue(borrow_vault.borrow_index)?;
+        
+        let collateral_vault = ctx.accounts.collateral_vault.projected(current_time)?;
+        let collateral_price_feed = ctx.accounts.collateral_price_feed.as_deref();
+        let borrow_price_feed = ctx.accounts.borrow_price_feed.as_deref();
+        let collateral_value = collateral_value(
+            &ctx.accounts.collateral_vault,
+            borrow_vault,
+            borrow_position.collateral_shares,
+            collateral_price_feed,
+            borrow_price_feed,
+            current_time,
+        )?;
+        let health_factor = borrow_position.health_factor(collateral_value, config.liquidation_threshold_bps)?;
+        require!(health_factor < WAD, VaultError::PositionHealthy);
+        
+        // Never less than one unit, or dust debt could not be liquidated at all
+        let max_repay = bps_of(borrow_position.debt, config.close_factor_bps)?.max(1);
+        let repay_amount = repay_amount.min(max_repay);
+        require!(repay_amount > 0, VaultError::InvalidAmount);
+        
+        let (interest_repaid, principal_repaid) = repay_debt(
+            borrow_vault,
+            borrow_position,
+            repay_amount,
+            &ctx.accounts.liquidator,
+            &ctx.accounts.liquidator_token_account,
+            &ctx.accounts.token_mint,
+            &mut ctx.accounts.vault_token_account,
+            &mut ctx.accounts.reward_reserve,
+            &ctx.accounts.token_program,
+        )?;
+        borrow_vault.refresh_rate()?;
+        let repaid = interest_repaid.safe_add(principal_repaid)?;
+        
+        // Repaid value plus the bonus, priced back into collateral shares
+        let seize_value = repaid.safe_add(bps_of(repaid, config.liquidation_bonus_bps)?)?;
+        let seize_assets = if collateral_vault.token_mint == borrow_vault.token_mint {
+            seize_value
+        } else {
+            let (collateral_feed, borrow_feed) = collateral_price_feed
+                .zip(borrow_price_feed)
+                .ok_or(VaultError::MissingPriceFeed)?;
+            lending::convert_at_prices(
+                seize_value,
+                borrow_feed.fresh_price(current_time)?,
+                borrow_feed.decimals,
+                collateral_feed.fresh_price(current_time)?,
+                collateral_feed.decimals,
+            )?
+        };
+        let seized_shares = collateral_vault
+            .convert_to_shares(seize_assets)?
+            .min(borrow_position.collateral_shares);
+        let seized_basis = to_u64(
+            (borrow_position.collateral_basis as u128)
+                .safe_mul(seized_shares as u128)?
+                .checked_div(borrow_position.collateral_shares as u128)
+                .unwrap_or(0),
+        )?;
+        borrow_position.collateral_shares = borrow_position.collateral_shares.safe_sub(seized_shares)?;
+        borrow_position.collateral_basis = borrow_position.collateral_basis.safe_sub(seized_basis)?;
+        
+        let liquidator_position = &mut ctx.accounts.liquidator_position;
+        liquidator_position.bind(ctx.accounts.liquidator.key(), ctx.accounts.collateral_vault.key())?;
+        settle_rewards(
+            &ctx.accounts.collateral_vault,
+            ctx.remaining_accounts,
+            liquidator_position,
+            current_time,
+        )?;
+        liquidator_position.shares = liquidator_position.shares.safe_add(seized_shares)?;
+        liquidator_position.deposited_amount = liquidator_position.deposited_amount.safe_add(seized_basis)?;
+        liquidator_position.last_update_time = current_time;
+        
+        let collateral_vault = &mut ctx.accounts.collateral_vault;
+        collateral_vault.total_collateral_shares = collateral_vault.total_collateral_shares.safe_sub(seized_shares)?;
+        
+        emit!(LiquidationEvent {
+            liquidator: ctx.accounts.liquidator.key(),
+            borrower: ctx.accounts.borrower.key(),
+            borrow_vault: borrow_vault.key(),
+            collateral_vault: borrow_position.collateral_vault,
+            interest_amount: interest_repaid,
+            principal_amount: principal_repaid,
+            seized_shares,
+            health_factor,
+            debt: borrow_position.debt,
+            timestamp: current_time,
+        });
+        
+        msg!("Liquidated {} of debt for {} collateral shares. Remaining debt: {}", repaid, seized_shares, borrow_position.debt);
+        Ok(())
+    }
+
+    /// Set the LTV, liquidation and close-factor parameters this vault lends under.
+    /// A zero `max_ltv_bps` disables new borrows; existing positions can still be liquidated.
+    pub fn set_borrow_config(ctx: Context<SetBorrowConfig>, borrow_config: BorrowConfig) -> Result<()> {
+        borrow_config.validate()?;
+        
+        let vault = &mut ctx.accounts.vault;
+        vault.borrow_config = borrow_config;
+        
+        emit!(BorrowConfigUpdatedEvent {
+            vault: vault.key(),
+            authority: ctx.accounts.authority.key(),
+            borrow_config,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!(
+            "Borrowing set to a max LTV of {}bps, liquidation at {}bps",
+            borrow_config.max_ltv_bps,
+            borrow_config.liquidation_threshold_bps
+        );
+        Ok(())
+    }
+
+    /// Accept shares of `collateral_vault` as collateral for loans out of `borrow_vault`, or
+    /// stop accepting them for new borrows with `enabled = false`. Existing positions can
+    /// always be repaid, released and liquidated.
+    ///
+    /// Anyone can create a vault and inflate its share price, so the borrow vault's authority
+    /// decides which vaults it lends against. Collateral in another mint is priced by
+    /// `price_feed`, which must be a feed that authority pushes itself.
+    pub fn set_collateral_config(ctx: Context<SetCollateralConfig>, enabled: bool) -> Result<()> {
+        let collateral_config = &mut ctx.accounts.collateral_config;
+        collateral_config.borrow_vault = ctx.accounts.borrow_vault.key();
+        collateral_config.collateral_vault = ctx.accounts.collateral_vault.key();
+        collateral_config.price_feed = ctx.accounts.price_feed.as_ref().map(|feed| feed.key());
+        collateral_config.enabled = enabled;
+        collateral_config.bump = ctx.bumps.collateral_config;
+        
+        emit!(CollateralConfigUpdatedEvent {
+            borrow_vault: collateral_config.borrow_vault,
+            collateral_vault: collateral_config.collateral_vault,
+            price_feed: collateral_config.price_feed,
+            enabled,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!(
+            "Collateral vault {} {} with price feed {:?}",
+            collateral_config.collateral_vault,
+            if enabled { "accepted" } else { "closed to new borrows" },
+            collateral_config.price_feed
+        );
+        Ok(())
+    }
+
+    /// Point the vault at the price feed its token is valued by when it lends, or clear it
+    /// with `None`.
+    ///
+    /// Whoever pushes the price decides the borrow limits and liquidations of every loan out
+    /// of the vault, so only a feed the vault authority pushes itself is accepted. Feeds stop
+    /// being accepted once the authority changes, so a new authority must set its own here
+    /// and in each `CollateralConfig`.
+    pub fn set_price_feed(ctx: Context<SetPriceFeed>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.price_feed = ctx.accounts.price_feed.as_ref().map(|feed| feed.key());
+        
+        emit!(PriceFeedUpdatedEvent {
+            vault: vault.key(),
+            price_feed: vault.price_feed,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Price feed set to {:?}", vault.price_feed);
+        Ok(())
+    }
+
+    /// Create a price feed for `token_mint` that only the caller can update
+    pub fn initialize_price_feed(ctx: Context<InitializePriceFeed>, price: u64) -> Result<()> {
+        require!(price > 0, VaultError::InvalidAmount);
+        
+        let price_feed = &mut ctx.accounts.price_feed;
+        let current_time = Clock::get()?.unix_timestamp;
+        price_feed.authority = ctx.accounts.authority.key();
+        price_feed.token_mint = ctx.accounts.token_mint.key();
+        price_feed.decimals = ctx.accounts.token_mint.decimals;
+        price_feed.price = price;
+        price_feed.updated_at = current_time;
+        price_feed.bump = ctx.bumps.price_feed;
+        
+        emit!(PriceUpdatedEvent {
+            price_feed: price_feed.key(),
+            price,
+            timestamp: current_time,
+        });
+        
+        msg!("Price feed initialized at {}", price);
+        Ok(())
+    }
+
+    /// Push a new price, in quote units per whole token
+    pub fn update_price(ctx: Context<UpdatePrice>, price: u64) -> Result<()> {
+        require!(price > 0, VaultError::InvalidAmount);
+        
+        let price_feed = &mut ctx.accounts.price_feed;
+        let current_time = Clock::get()?.unix_timestamp;
+        price_feed.price = price;
+        price_feed.updated_at = current_time;
+        
+        emit!(PriceUpdatedEvent {
+            price_feed: price_feed.key(),
+            price,
+            timestamp: current_time,
+        });
+        
+        msg!("Price updated to {}", price);
+        Ok(())
+    }
+
+    /// Add a reward stream in the first free slot, emitting `emission_rate` reward tokens per
+    /// second to the vault's shareholders between `start_time` and `end_time`. The whole
+    /// emission is escrowed from the authority up front; a `start_time` in the past starts
+    /// the stream now, so no emission is escrowed for time that has already gone by.
+    pub fn add_reward_stream(
+        ctx: Context<AddRewardStream>,
+        index: u8,
+        emission_rate: u64,
+        start_time: i64,
+        end_time: i64,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let vault = &mut ctx.accounts.vault;
+        require!(vault.sunset_deadline.is_none(), VaultError::VaultSunsetting);
+        require!(
+            vault.reward_streams.iter().position(Option::is_none) == Some(index as usize),
+            VaultError::InvalidRewardStream
+        );
+        require!(emission_rate > 0, VaultError::InvalidRewardStream);
+        require!(
+            start_time < end_time && end_time > current_time,
+            VaultError::InvalidRewardStream
+        );
+        let start_time = start_time.max(current_time);
+        
+        fund_reward_escrow(
+            &ctx.accounts.authority,
+            &ctx.accounts.authority_reward_account,
+            &ctx.accounts.reward_mint,
+            &mut ctx.accounts.reward_escrow,
+            &ctx.accounts.reward_token_program,
+            emission_rate.safe_mul(end_time.safe_sub(start_time)? as u64)?,
+        )?;
+        
+        let reward_stream = &mut ctx.accounts.reward_stream;
+        reward_stream.vault = vault.key();
+        reward_stream.index = index;
+        reward_stream.reward_mint = ctx.accounts.reward_mint.key();
+        reward_stream.reward_escrow = ctx.accounts.reward_escrow.key();
+        reward_stream.reward_token_program = ctx.accounts.reward_token_program.key();
+        reward_stream.emission_rate = emission_rate;
+        reward_stream.start_time = start_time;
+        reward_stream.end_time = end_time;
+        // A slot's account outlives its streams, and positions have settled against its
+        // `reward_per_share`, so a new stream carries on from the last one's index
+        reward_stream.unclaimed_rewards = 0;
+        reward_stream.last_update_time = current_time;
+        reward_stream.bump = ctx.bumps.reward_stream;
+        
+        vault.reward_streams[index as usize] = Some(reward_stream.key());
+        
+        emit!(RewardStreamAddedEvent {
+            vault: vault.key(),
+            reward_stream: reward_stream.key(),
+            index,
+            reward_mint: reward_stream.reward_mint,
+            emission_rate,
+            start_time,
+            end_time,
+            timestamp: current_time,
+        });
+        
+        msg!("Reward stream {} added: {} per second from {} to {}", index, emission_rate, start_time, end_time);
+        Ok(())
+    }
+
+    /// Push a stream's `end_time` out to `new_end_time` at the same rate, escrowing the
+    /// extra emission from the authority. An ended stream restarts from now.
+    pub fn extend_reward_stream(ctx: Context<ExtendRewardStream>, new_end_time: i64) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let reward_stream = &mut ctx.accounts.reward_stream;
+        
+        // Close out emissions at the old schedule; nothing is owed for any gap since it ended
+        reward_stream.update(ctx.accounts.vault.reward_shares()?, current_time)?;
+        let extend_from = reward_stream.end_time.max(current_time);
+        require!(new_end_time > extend_from, VaultError::InvalidRewardStream);
+        
+        fund_reward_escrow(
+            &ctx.accounts.authority,
+            &ctx.accounts.authority_reward_account,
+            &ctx.accounts.reward_mint,
+            &mut ctx.accounts.reward_escrow,
+            &ctx.accounts.reward_token_program,
+            reward_stream.emission_rate.safe_mul(new_end_time.safe_sub(extend_from)? as u64)?,
+        )?;
+        
+        let old_end_time = reward_stream.end_time;
+        reward_stream.end_time = new_end_time;
+        
+        emit!(RewardStreamExtendedEvent {
+            vault: ctx.accounts.vault.key(),
+            reward_stream: reward_stream.key(),
+            old_end_time,
+            new_end_time,
+            timestamp: current_time,
+        });
+        
+        msg!("Reward stream {} extended to {}", reward_stream.index, new_end_time);
+        Ok(())
+    }
+
+    /// Return whatever an ended stream never emitted to shareholders, such as emissions
+    /// while the vault had no shares, from its escrow to the authority
+    pub fn sweep_reward_stream(ctx: Context<SweepRewardStream>) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let reward_stream = &mut ctx.accounts.reward_stream;
+        require!(current_time >= reward_stream.end_time, VaultError::RewardStreamActive);
+        
+        reward_stream.update(ctx.accounts.vault.reward_shares()?, current_time)?;
+        // Everything emitted stays behind for shareholders to claim
+        let amount = ctx
+            .accounts
+            .reward_escrow
+            .amount
+            .saturating_sub(reward_stream.unclaimed_rewards);
+        require!(amount > 0, VaultError::InvalidAmount);
+        
+        let vault_key = ctx.accounts.vault.key();
+        let seeds = &[b"reward-stream", vault_key.as_ref(), &[reward_stream.index], &[reward_stream.bump]];
+        let signer = &[&seeds[..]];
+        
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.reward_escrow.to_account_info(),
+            mint: ctx.accounts.reward_mint.to_account_info(),
+            to: ctx.accounts.authority_reward_account.to_account_info(),
+            authority: reward_stream.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.reward_token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        );
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.reward_mint.decimals)?;
+        
+        emit!(RewardStreamSweptEvent {
+            vault: vault_key,
+            reward_stream: reward_stream.key(),
+            amount,
+            timestamp: current_time,
+        });
+        
+        msg!("Swept {} undistributed reward tokens from stream {}", amount, reward_stream.index);
+        Ok(())
+    }
+
+    /// Retire an ended stream once shareholders have claimed everything it emitted: return
+    /// what is left in its escrow to the authority, close the escrow and free the slot for a
+    /// new stream. Past the vault's sunset deadline unclaimed rewards are forfeited, like any
+    /// other unclaimed balance, and the stream may be retired before it ends.
+    pub fn close_reward_stream(ctx: Context<CloseRewardStream>) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let vault = &mut ctx.accounts.vault;
+        let reward_stream = &mut ctx.accounts.reward_stream;
+        
+        let forfeit = vault.sunset_deadline.is_some_and(|deadline| current_time >= deadline);
+        require!(
+            current_time >= reward_stream.end_time || forfeit,
+            VaultError::RewardStreamActive
+        );
+        reward_stream.update(vault.reward_shares()?, current_time)?;
+        require!(
+            reward_stream.unclaimed_rewards == 0 || forfeit,
+            VaultError::RewardsUnclaimed
+        );
+        
+        let vault_key = vault.key();
+        let seeds = &[b"reward-stream", vault_key.as_ref(), &[reward_stream.index], &[reward_stream.bump]];
+        let signer = &[&seeds[..]];
+        
+        let amount = ctx.accounts.reward_escrow.amount;
+        if amount > 0 {
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.reward_escrow.to_account_info(),
+                mint: ctx.accounts.reward_mint.to_account_info(),
+                to: ctx.accounts.authority_reward_account.to_account_info(),
+                authority: reward_stream.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.reward_token_program.to_account_info(),
+                cpi_accounts,
+                signer,
+            );
+            token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.reward_mint.decimals)?;
+        }
+        
+        let cpi_accounts = CloseAccount {
+            account: ctx.accounts.reward_escrow.to_account_info(),
+            destination: ctx.accounts.authority.to_account_info(),
+            authority: reward_stream.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.reward_token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        );
+        token_interface::close_account(cpi_ctx)?;
+        
+        reward_stream.unclaimed_rewards = 0;
+        vault.reward_streams[reward_stream.index as usize] = None;
+        
+        emit!(RewardStreamClosedEvent {
+            vault: vault_key,
+            reward_stream: reward_stream.key(),
+            swept_amount: amount,
+            timestamp: current_time,
+        });
+        
+        msg!("Reward stream {} closed. Swept {} reward tokens", reward_stream.index, amount);
+        Ok(())
+    }
+
+    /// Pay out the caller's settled and newly earned rewards from one stream
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let stream_info = ctx.accounts.reward_stream.to_account_info();
+        
+        let amount = claim_from_stream(
+            &ctx.accounts.vault,
+            &mut ctx.accounts.reward_stream,
+            stream_info,
+            &mut ctx.accounts.user_position,
+            ctx.accounts.reward_mint.to_account_info(),
+            ctx.accounts.reward_mint.decimals,
+            ctx.accounts.reward_escrow.to_account_info(),
+            ctx.accounts.user_reward_account.to_account_info(),
+            ctx.accounts.reward_token_program.to_account_info(),
+            ctx.accounts.user.key(),
+            current_time,
+        )?;
+        require!(amount > 0, VaultError::NoRewardsToClaim);
+        
+        msg!("Claimed {} reward tokens", amount);
+        Ok(())
+    }
+
+    /// Pay out the caller's rewards from several streams at once. Each stream is passed in
+    /// the remaining accounts as `[reward_stream, reward_mint, reward_escrow,
+    /// user_reward_account, reward_token_program]`.
+    pub fn claim_all_rewards<'info>(ctx: Context<'_, '_, '_, 'info, ClaimAllRewards<'info>>) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let groups = ctx.remaining_accounts.chunks_exact(5);
+        require!(
+            groups.remainder().is_empty() && groups.len() > 0,
+            VaultError::InvalidRewardStream
+        );
+        
+        let mut total = 0u64;
+        for group in groups {
+            let [stream_info, mint_info, escrow_info, user_reward_info, token_program_info] = group else {
+                return err!(VaultError::InvalidRewardStream);
+            };
+            require!(
+                stream_info.owner == &crate::ID && stream_info.is_writable,
+                VaultError::InvalidRewardStream
+            );
+            let mut reward_stream = RewardStream::try_deserialize(&mut &stream_info.try_borrow_data()?[..])?;
+            require!(
+                ctx.accounts.vault.reward_streams.get(reward_stream.index as usize)
+                    == Some(&Some(stream_info.key())),
+                VaultError::InvalidRewardStream
+            );
+            let reward_mint = Mint::try_deserialize(&mut &mint_info.try_borrow_data()?[..])?;
+            
+            let amount = claim_from_stream(
+                &ctx.accounts.vault,
+                &mut reward_stream,
+                stream_info.clone(),
+                &mut ctx.accounts.user_position,
+                mint_info.clone(),
+                reward_mint.decimals,
+                escrow_info.clone(),
+                user_reward_info.clone(),
+                token_program_info.clone(),
+                ctx.accounts.user.key(),
+                current_time,
+            )?;
+            reward_stream.try_serialize(&mut &mut stream_info.try_borrow_mut_data()?[..])?;
+            total = total.safe_add(amount)?;
+        }
+        require!(total > 0, VaultError::NoRewardsToClaim);
+        
+        msg!("Claimed {} reward tokens across streams", total);
+        Ok(())
+    }
+
+    /// Lend `amount` from the vault token account for the rest of the transaction.
+    ///
+    /// A `flash_repay` for this vault must appear later in the same transaction; the
+    /// instructions sysvar is checked for it up front, so a transaction without one fails
+    /// here rather than leaving the loan open.
+    pub fn flash_borrow(ctx: Context<FlashBorrow>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(amount > 0, VaultError::InvalidAmount);
+        require!(!vault.is_paused(PAUSE_WITHDRAWALS), VaultError::Paused);
+        require!(vault.flash_loan_amount == 0, VaultError::FlashLoanActive);
+        require!(
+            amount <= ctx.accounts.vault_token_account.amount,
+            VaultError::InsufficientLiquidity
+        );
+        
+        // Called via CPI, the current index would point at the caller's instruction instead
+        let instructions = ctx.accounts.instructions.to_account_info();
+        let current_index = load_current_index_checked(&instructions)? as usize;
+        let current = load_instruction_at_checked(current_index, &instructions)?;
+        require_keys_eq!(current.program_id, crate::ID, VaultError::InvalidFlashLoan);
+        
+        let mut index = current_index + 1;
+        let repaid_later = loop {
+            let Ok(instruction) = load_instruction_at_checked(index, &instructions) else {
+                break false;
+            };
+            if instruction.program_id == crate::ID
+                && instruction.data.get(..8) == Some(&instruction::FlashRepay::DISCRIMINATOR[..])
+                && instruction.accounts.first().map(|meta| meta.pubkey) == Some(vault.key())
+            {
+                break true;
+            }
+            index += 1;
+        };
+        require!(repaid_later, VaultError::InvalidFlashLoan);
+        
+        let fee = bps_of(amount, vault.fees.flash_loan_fee_bps)?;
+        vault.flash_loan_amount = amount;
+        vault.flash_loan_fee = fee;
+        
+        let id_seed = vault.id_seed();
+        let seeds = &[
+            b"vault",
+            vault.token_mint.as_ref(),
+            id_seed.as_ref(),
+            &[vault.bump],
+        ];
+        let signer = &[&seeds[..]];
+        
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_token_account.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            to: ctx.accounts.borrower_token_account.to_account_info(),
+            authority: vault.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.token_mint.decimals)?;
+        
+        emit!(FlashBorrowEvent {
+            borrower: ctx.accounts.borrower.key(),
+            vault: vault.key(),
+            amount,
+            fee,
+            timestamp: current_time,
+        });
+        
+        msg!("Flash borrowed {} tokens, {} fee due", amount, fee);
+        Ok(())
+    }
+
+    /// Close the transaction's flash loan: the principal goes back to the vault token
+    /// account and the fee to the reward reserve, where it is credited to depositors as
+    /// interest less the performance fee, which goes to the treasury.
+    pub fn flash_repay(ctx: Context<FlashRepay>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        let amount = vault.flash_loan_amount;
+        let fee = vault.flash_loan_fee;
+        require!(amount > 0, VaultError::NoFlashLoan);
+        
+        let vault_before = ctx.accounts.vault_token_account.amount;
+        let reserve_before = ctx.accounts.reward_reserve.amount;
+        
+        for (to, leg) in [
+            (ctx.accounts.vault_token_account.to_account_info(), amount),
+            (ctx.accounts.reward_reserve.to_account_info(), fee),
+        ] {
+            if leg > 0 {
+                let cpi_accounts = TransferChecked {
+                    from: ctx.accounts.payer_token_account.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to,
+                    authority: ctx.accounts.payer.to_account_info(),
+                };
+                let cpi_program = ctx.accounts.token_program.to_account_info();
+                let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+                token_interface::transfer_checked(cpi_ctx, leg, ctx.accounts.token_mint.decimals)?;
+            }
+        }
+        
+        // Transfer-fee mints withhold part of each leg; the vault must still be made whole
+        ctx.accounts.vault_token_account.reload()?;
+        ctx.accounts.reward_reserve.reload()?;
+        require!(
+            ctx.accounts.vault_token_account.amount.safe_sub(vault_before)? >= amount
+                && ctx.accounts.reward_reserve.amount.safe_sub(reserve_before)? >= fee,
+            VaultError::InvalidFlashLoan
+        );
+        
+        // Interest before the fee lands belongs to existing depositors at the usual rate
+        accrue_vault(vault, current_time)?;
+        let treasury_fee = bps_of(fee, vault.fees.performance_fee_bps)?;
+        vault.accrued_reserve_fees = vault.accrued_reserve_fees.safe_add(treasury_fee)?;
+        vault.total_accrued_interest = vault
+            .total_accrued_interest
+            .safe_add(fee.safe_sub(treasury_fee)?)?;
+        vault.flash_loan_amount = 0;
+        vault.flash_loan_fee = 0;
+        
+        if fee > 0 {
+            emit!(FeeChargedEvent {
+                vault: vault.key(),
+                user: Some(ctx.accounts.payer.key()),
+                kind: FeeKind::FlashLoan,
+                amount: fee,
+                timestamp: current_time,
+            });
+        }
+        
+        emit!(FlashRepayEvent {
+            payer: ctx.accounts.payer.key(),
+            vault: vault.key(),
+            amount,
+            fee,
+            treasury_fee,
+            timestamp: current_time,
+        });
+        
+        msg!("Flash loan of {} repaid with {} fee ({} to the treasury)", amount, fee, treasury_fee);
+        Ok(())
+    }
+
+    /// Register `strategy_program` as a place the vault may deploy up to `allocation_cap`
+    /// tokens of idle principal, sent to `deposit_account`, a token account the strategy controls
+    pub fn add_strategy(ctx: Context<AddStrategy>, allocation_cap: u64) -> Result<()> {
+        let strategy = &mut ctx.accounts.strategy;
+        strategy.vault = ctx.accounts.vault.key();
+        strategy.strategy_program = ctx.accounts.strategy_program.key();
+        strategy.deposit_account = ctx.accounts.deposit_account.key();
+        strategy.allocation_cap = allocation_cap;
+        strategy.allocated = 0;
+        strategy.total_harvested = 0;
+        strategy.bump = ctx.bumps.strategy;
+        
+        emit!(StrategyAddedEvent {
+            vault: strategy.vault,
+            strategy: strategy.key(),
+            strategy_program: strategy.strategy_program,
+            deposit_account: strategy.deposit_account,
+            allocation_cap,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Strategy {} added with a cap of {}", strategy.strategy_program, allocation_cap);
+        Ok(())
+    }
+
+    /// Change how much a strategy may hold. Lowering the cap below what is allocated
+    /// only stops new allocations; `deallocate` brings the funds back.
+    pub fn set_allocation_cap(ctx: Context<SetAllocationCap>, allocation_cap: u64) -> Result<()> {
+        let strategy = &mut ctx.accounts.strategy;
+        let old_cap = strategy.allocation_cap;
+        strategy.allocation_cap = allocation_cap;
+        
+        emit!(AllocationCapUpdatedEvent {
+            strategy: strategy.key(),
+            old_cap,
+            new_cap: allocation_cap,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Allocation cap changed from {} to {}", old_cap, allocation_cap);
+        Ok(())
+    }
+
+    /// Set how large a strategy loss may be, in basis points of total assets, before
+    /// `report` pauses deposits; `None` never pauses
+    pub fn set_loss_threshold(ctx: Context<SetLossThreshold>, loss_threshold_bps: Option<u16>) -> Result<()> {
+        if let Some(bps) = loss_threshold_bps {
+            require!(bps as u128 <= interest::BPS_DENOMINATOR, VaultError::InvalidLossThreshold);
+        }
+        
+        let vault = &mut ctx.accounts.vault;
+        let old_threshold_bps = vault.loss_threshold_bps;
+        vault.loss_threshold_bps = loss_threshold_bps;
+        
+        emit!(LossThresholdUpdatedEvent {
+            vault: vault.key(),
+            authority: ctx.accounts.authority.key(),
+            old_threshold_bps,
+            new_threshold_bps: loss_threshold_bps,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Loss threshold set to {:?} bps", loss_threshold_bps);
+        Ok(())
+    }
+
+    /// Move `amount` of idle principal from the vault token account into the strategy.
+    ///
+    /// The vault transfers the tokens to the strategy's deposit account itself and only then
+    /// tells the strategy, so the strategy never needs authority over the vault's accounts.
+    pub fn allocate<'info>(ctx: Context<'_, '_, '_, 'info, StrategyOperation<'info>>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        let strategy = &mut ctx.accounts.strategy;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(amount > 0, VaultError::InvalidAmount);
+        require!(vault.flash_loan_amount == 0, VaultError::FlashLoanActive);
+        require!(vault.sunset_deadline.is_none(), VaultError::VaultSunsetting);
+        require!(
+            strategy.allocated.safe_add(amount)? <= strategy.allocation_cap,
+            VaultError::AllocationCapExceeded
+        );
+        
+        accrue_vault(vault, current_time)?;
+        require!(amount <= vault.available_liquidity(), VaultError::InsufficientLiquidity);
+        
+        let id_seed = vault.id_seed();
+        let seeds = &[
+            b"vault",
+            vault.token_mint.as_ref(),
+            id_seed.as_ref(),
+            &[vault.bump],
+        ];
+        let signer = &[&seeds[..]];
+        
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_token_account.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            to: ctx.accounts.deposit_account.to_account_info(),
+            authority: vault.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.token_mint.decimals)?;
+        
+        invoke_strategy(
+            strategy,
+            &ctx.accounts.strategy_program,
+            strategy::DEPOSIT_FUNDS,
+            Some(amount),
+            &ctx.accounts.token_mint,
+            &mut ctx.accounts.vault_token_account,
+            &mut ctx.accounts.reward_reserve,
+            &ctx.accounts.token_program,
+            ctx.remaining_accounts,
+        )?;
+        
+        strategy.allocated = strategy.allocated.safe_add(amount)?;
+        vault.total_allocated = vault.total_allocated.safe_add(amount)?;
+        vault.refresh_rate()?;
+        
+        emit!(AllocationEvent {
+            strategy: strategy.key(),
+            amount,
+            allocated: strategy.allocated,
+            timestamp: current_time,
+        });
+        
+        msg!("Allocated {} tokens. Strategy now holds {}", amount, strategy.allocated);
+        Ok(())
+    }
+
+    /// Bring `amount` of principal back from the strategy into the vault token account.
+    /// Anything returned on top of `amount` is a gain for depositors.
+    pub fn deallocate<'info>(ctx: Context<'_, '_, '_, 'info, StrategyOperation<'info>>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        let strategy = &mut ctx.accounts.strategy;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(amount > 0, VaultError::InvalidAmount);
+        require!(amount <= strategy.allocated, VaultError::InsufficientBalance);
+        
+        accrue_vault(vault, current_time)?;
+        
+        let balance_before = ctx.accounts.vault_token_account.amount;
+        invoke_strategy(
+            strategy,
+            &ctx.accounts.strategy_program,
+            strategy::WITHDRAW_FUNDS,
+            Some(amount),
+            &ctx.accounts.token_mint,
+            &mut ctx.accounts.vault_token_account,
+            &mut ctx.accounts.reward_reserve,
+            &ctx.accounts.token_program,
+            ctx.remaining_accounts,
+        )?;
+        let received = ctx.accounts.vault_token_account.amount.safe_sub(balance_before)?;
+        require!(received >= amount, VaultError::StrategyMismatch);
+        let gain = received.safe_sub(amount)?;
+        
+        strategy.allocated = strategy.allocated.safe_sub(amount)?;
+        vault.total_allocated = vault.total_allocated.safe_sub(amount)?;
+        vault.total_deposited = vault.total_deposited.safe_add(gain)?;
+        vault.refresh_rate()?;
+        
+        emit!(DeallocationEvent {
+            strategy: strategy.key(),
+            amount,
+            gain,
+            allocated: strategy.allocated,
+            timestamp: current_time,
+        });
+        
+        msg!("Deallocated {} tokens with a gain of {}. Strategy now holds {}", amount, gain, strategy.allocated);
+        Ok(())
+    }
+
+    /// Have the strategy send its realized yield to the reward reserve and credit it to
+    /// depositors as interest, less the performance fee
+    pub fn harvest<'info>(ctx: Context<'_, '_, '_, 'info, StrategyOperation<'info>>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        let strategy = &mut ctx.accounts.strategy;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        accrue_vault(vault, current_time)?;
+        
+        let reserve_before = ctx.accounts.reward_reserve.amount;
+        invoke_strategy(
+            strategy,
+            &ctx.accounts.strategy_program,
+            strategy::HARVEST,
+            None,
+            &ctx.accounts.token_mint,
+            &mut ctx.accounts.vault_token_account,
+            &mut ctx.accounts.reward_reserve,
+            &ctx.accounts.token_program,
+            ctx.remaining_accounts,
+        )?;
+        let harvested = ctx.accounts.reward_reserve.amount.safe_sub(reserve_before)?;
+        
+        let treasury_fee = bps_of(harvested, vault.fees.performance_fee_bps)?;
+        vault.accrued_reserve_fees = vault.accrued_reserve_fees.safe_add(treasury_fee)?;
+        vault.total_accrued_interest = vault
+            .total_accrued_interest
+            .safe_add(harvested.safe_sub(treasury_fee)?)?;
+        vault.refresh_rate()?;
+        strategy.total_harvested = strategy.total_harvested.safe_add(harvested)?;
+        
+        if treasury_fee > 0 {
+            emit!(FeeChargedEvent {
+                vault: vault.key(),
+                user: None,
+                kind: FeeKind::Performance,
+                amount: treasury_fee,
+                timestamp: current_time,
+            });
+        }
+        
+        emit!(HarvestEvent {
+            strategy: strategy.key(),
+            amount: harvested,
+            total_harvested: strategy.total_harvested,
+            timestamp: current_time,
+        });
+        
+        msg!("Harvested {} tokens into the reward reserve ({} to the treasury)", harvested, treasury_fee);
+        Ok(())
+    }
+
+    /// Mark a strategy's holdings to what it is actually worth.
+    ///
+    /// A `gain` the strategy keeps is added to the principal it holds for the vault, and a
+    /// `loss` is written off it. Either way the change lands in total assets, so every share
+    /// gains or loses value in proportion and no single depositor absorbs a loss by being
+    /// the last to withdraw. Yield that `harvest` moves to the reward reserve must not also
+    /// be reported as a gain.
+    ///
+    /// A loss larger than `loss_threshold_bps` of total assets pauses deposits, leaving
+    /// the vault withdraw-only until the authority lifts the pause.
+    pub fn report(ctx: Context<ReportStrategy>, gain: u64, loss: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        let strategy = &mut ctx.accounts.strategy;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(gain == 0 || loss == 0, VaultError::InvalidAmount);
+        require!(loss <= strategy.allocated, VaultError::InsufficientBalance);
+        require!(vault.flash_loan_amount == 0, VaultError::FlashLoanActive);
+        
+        accrue_vault(vault, current_time)?;
+        let assets_before = vault.total_assets()?;
+        
+        strategy.allocated = strategy.allocated.safe_add(gain)?.safe_sub(loss)?;
+        vault.total_allocated = vault.total_allocated.safe_add(gain)?.safe_sub(loss)?;
+        vault.total_deposited = vault.total_deposited.safe_add(gain)?.safe_sub(loss)?;
+        
+        let threshold_breached = match vault.loss_threshold_bps {
+            Some(bps) => loss > bps_of(assets_before, bps)?,
+            None => false,
+        };
+        if threshold_breached {
+            vault.paused_flags |= PAUSE_DEPOSITS;
+        }
+        vault.refresh_rate()?;
+        
+        emit!(StrategyReportEvent {
+            vault: vault.key(),
+            strategy: strategy.key(),
+            gain,
+            loss,
+            allocated: strategy.allocated,
+            total_assets: vault.total_assets()?,
+            withdraw_only: vault.is_paused(PAUSE_DEPOSITS),
+            timestamp: current_time,
+        });
+        
+        msg!(
+            "Strategy reported a gain of {} and a loss of {}. Total assets: {}, withdraw-only: {}",
+            gain,
+            loss,
+            vault.total_assets()?,
+            vault.is_paused(PAUSE_DEPOSITS)
+        );
+        Ok(())
+    }
+
+    /// Start winding the vault down: deposits stop for good and users have until
+    /// `grace_period` seconds from now to withdraw before `close_vault` may run.
+    ///
+    /// The deadline must fall after every open lock matures so locked users get the
+    /// same window to exit without a penalty.
+    pub fn begin_sunset(ctx: Context<BeginSunset>, grace_period: i64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(vault.sunset_deadline.is_none(), VaultError::VaultSunsetting);
+        require!(grace_period >= 0, VaultError::InvalidSunsetDeadline);
+        
+        let deadline = current_time.safe_add(grace_period)?;
+        require!(deadline >= vault.latest_unlock_time, VaultError::InvalidSunsetDeadline);
+        
+        vault.sunset_deadline = Some(deadline);
+        
+        emit!(SunsetStartedEvent {
+            vault: vault.key(),
+            authority: ctx.accounts.authority.key(),
+            deadline,
+            timestamp: current_time,
+        });
+        
+        msg!("Vault sunset started. Withdraw before: {}", deadline);
+        Ok(())
+    }
+
+    /// Close a sunset vault, sweeping whatever is left in its token accounts to the authority.
+    ///
+    /// Before the deadline this only runs once every depositor has left; afterwards any
+    /// unclaimed balance is forfeited. Call `collect_fees` first to route fees to the treasury.
+    /// It never runs while loans are outstanding, its shares secure loans elsewhere or funds
+    /// are deployed to strategies, since all of them need this vault to unwind, or while any reward stream is still open,
+    /// since claims and sweeps need the vault; retire them with `close_reward_stream` first.
+    pub fn close_vault(ctx: Context<CloseVault>) -> Result<()> {
+        let vault = &ctx.accounts.vault;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        let deadline = vault.sunset_deadline.ok_or(VaultError::VaultNotSunsetting)?;
+        // Open borrow positions need this vault to repay and free their collateral
+        require!(vault.total_borrowed == 0, VaultError::OutstandingBorrows);
+        // So do loans elsewhere secured by this vault's shares
+        require!(vault.total_collateral_shares == 0, VaultError::OutstandingCollateral);
+        // Strategies can only return funds through this vault
+        require!(vault.total_allocated == 0, VaultError::StrategyFundsOutstanding);
+        // Reward escrows can only be claimed from or swept while the vault exists
+        require!(
+            vault.reward_streams.iter().all(Option::is_none),
+            VaultError::RewardStreamsOpen
+        );
+        if current_time < deadline {
+            require!(
+                vault.total_deposited == 0
+                    && vault.total_locked == 0
+                    && vault.pending_principal == 0
+                    && vault.pending_interest == 0,
+                VaultError::SunsetInProgress
+            );
+        }
+        
+        let id_seed = vault.id_seed();
+        let seeds = &[
+            b"vault",
+            vault.token_mint.as_ref(),
+            id_seed.as_ref(),
+            &[vault.bump],
+        ];
+        let signer = &[&seeds[..]];
+        
+        let mut swept_amount = 0u64;
+        for token_account in [&ctx.accounts.vault_token_account, &ctx.accounts.reward_reserve] {
+            if token_account.amount > 0 {
+                let cpi_accounts = TransferChecked {
+                    from: token_account.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.authority_token_account.to_account_info(),
+                    authority: vault.to_account_info(),
+                };
+                let cpi_program = ctx.accounts.token_program.to_account_info();
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+                token_interface::transfer_checked(cpi_ctx, token_account.amount, ctx.accounts.token_mint.decimals)?;
+                swept_amount = swept_amount.safe_add(token_account.amount)?;
+            }
+            
+            let cpi_accounts = CloseAccount {
+                account: token_account.to_account_info(),
+                destination: ctx.accounts.authority.to_account_info(),
+                authority: vault.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token_interface::close_account(cpi_ctx)?;
+        }
+        
+        emit!(VaultClosedEvent {
+            vault: vault.key(),
+            authority: ctx.accounts.authority.key(),
+            swept_amount,
+            timestamp: current_time,
+        });
+        
+        msg!("Vault closed. Swept {} unclaimed tokens to the authority", swept_amount);
+        Ok(())
+    }
+
+    /// Get user's current balance including accrued interest
+    pub fn get_user_balance(ctx: Context<GetUserBalance>) -> Result<UserBalanceInfo> {
+        let user_position = &ctx.accounts.user_position;
+        let vault = ctx.accounts.vault.projected(Clock::get()?.unix_timestamp)?;
+        
+        let total_balance = vault.convert_to_assets(user_position.shares)?;
+        
+        Ok(UserBalanceInfo {
+            deposited_amount: user_position.deposited_amount,
+            accrued_interest: total_balance.saturating_sub(user_position.deposited_amount),
+            total_balance,
+            shares: user_position.shares,
+            last_update_time: user_position.last_update_time,
+        })
+    }
+
+    /// Get the number of shares a deposit of `amount` would mint right now
+    pub fn preview_deposit(ctx: Context<VaultView>, amount: u64) -> Result<u64> {
+        let vault = ctx.accounts.vault.projected(Clock::get()?.unix_timestamp)?;
+        vault.convert_to_shares(amount)
+    }
+
+    /// Get the number of shares a withdrawal of `amount` would burn right now
+    pub fn preview_withdraw(ctx: Context<VaultView>, amount: u64) -> Result<u64> {
+        let vault = ctx.accounts.vault.projected(Clock::get()?.unix_timestamp)?;
+        vault.preview_withdraw(amount)
+    }
+
+    /// Get the amount of tokens `shares` are currently worth
+    pub fn convert_to_assets(ctx: Context<VaultView>, shares: u64) -> Result<u64> {
+        let vault = ctx.accounts.vault.projected(Clock::get()?.unix_timestamp)?;
+        vault.convert_to_assets(shares)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(vault_id: u64)]
+pub struct InitializeVault<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + (1 + 32) + 32 + 8 + 32 + 32 + 32 + 32 + 8 + InterestModel::LEN + (1 + RateCurve::LEN) + 8 + 8 + 8
+            + 8 + 16 + 8 + BorrowConfig::LEN + (1 + 32) + (1 + 32) * MAX_REWARD_STREAMS + 8 + 8 + 8 + (1 + 2)
+            + FeeConfig::LEN
+            + (1 + 8) + (1 + 8) + 8 + (1 + 32) + OutflowLimit::LEN + 8 + 8 + 8 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + LockTerm::LEN * MAX_LOCK_TERMS + 8 + (1 + 8) + 1 + 8,
+        seeds = [b"vault", token_mint.key().as_ref(), vault_id_seed(vault_id).as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(mint::token_program = token_program)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        token::mint = token_mint,
+        token::authority = vault,
+        token::token_program = token_program,
+        seeds = [b"vault-token", token_mint.key().as_ref(), vault_id_seed(vault_id).as_ref()],
+        bump
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = authority,
+        token::mint = token_mint,
+        token::authority = vault,
+        token::token_program = token_program,
+        seeds = [b"vault-reserve", token_mint.key().as_ref(), vault_id_seed(vault_id).as_ref()],
+        bump
+    )]
+    pub reward_reserve: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(constraint = treasury.mint == token_mint.key() @ VaultError::InvalidTreasury)]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + (16 + 8) * MAX_REWARD_STREAMS,
+        seeds = [b"user-position", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: Account<'info, UserPosition>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(address = vault.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key(),
+        constraint = user_token_account.mint == vault.token_mint
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault-token", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = vault.token_program @ VaultError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+
+}
+
+#[derive(Accounts)]
+pub struct DepositLocked<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + (16 + 8) * MAX_REWARD_STREAMS,
+        seeds = [b"user-position", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: Account<'info, UserPosition>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = 8 + 32 + 32 + 8 + 1 + 8 + 8 + (1 + 2) + 8 + 8 + 1,
+        seeds = [
+            b"lock-position",
+            vault.key().as_ref(),
+            user.key().as_ref(),
+            user_position.lock_count.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub lock_position: Account<'info, LockPosition>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(address = vault.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key(),
+        constraint = user_token_account.mint == vault.token_mint
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault-token", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = vault.token_program @ VaultError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        seeds = [b"user-position", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: Account<'info, UserPosition>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(address = vault.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key(),
+        constraint = user_token_account.mint == vault.token_mint
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault-token", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault-reserve", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump
+    )]
+    pub reward_reserve: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = vault.token_program @ VaultError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+
+}
+
+#[derive(Accounts)]
+pub struct RequestWithdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        seeds = [b"user-position", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: Account<'info, UserPosition>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 32 + 8 + 8 + 8 + 1,
+        seeds = [b"pending-withdrawal", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+
+}
+
+#[derive(Accounts)]
+pub struct ClaimWithdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"pending-withdrawal", vault.key().as_ref(), user.key().as_ref()],
+        bump = pending_withdrawal.bump
+    )]
+    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(address = vault.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key(),
+        constraint = user_token_account.mint == vault.token_mint
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault-token", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault-reserve", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump
+    )]
+    pub reward_reserve: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = vault.token_program @ VaultError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawLocked<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        close = user,
+        seeds = [
+            b"lock-position",
+            vault.key().as_ref(),
+            user.key().as_ref(),
+            lock_position.lock_id.to_le_bytes().as_ref()
+        ],
+        bump = lock_position.bump
+    )]
+    pub lock_position: Account<'info, LockPosition>,
+    
+    #[account(
+        mut,
+        seeds = [b"user-position", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: Account<'info, UserPosition>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(address = vault.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key(),
+        constraint = user_token_account.mint == vault.token_mint
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault-token", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault-reserve", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump
+    )]
+    pub reward_reserve: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, address = vault.treasury @ VaultError::InvalidTreasury)]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = vault.token_program @ VaultError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ClosePosition<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"user-position", vault.key().as_ref(), user.key().as_ref()],
+        bump,
+        constraint = user_position.owner == user.key() @ VaultError::InvalidPosition
+    )]
+    pub user_position: Account<'info, UserPosition>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateVaultParams<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetRateCurve<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetFees<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetDepositCaps<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetWithdrawalCooldown<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetOutflowLimit<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    pub signer: Signer<'info>, // the authority, or the guardian when tightening
+}
+
+#[derive(Accounts)]
+pub struct SetLockTerm<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    pub pending_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAuthorityTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FundReserve<'info> {
+    #[account(
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    pub authority: Signer<'info>,
+    
+    #[account(address = vault.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = authority_token_account.owner == authority.key(),
+        constraint = authority_token_account.mint == vault.token_mint
+    )]
+    pub authority_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault-reserve", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump
+    )]
+    pub reward_reserve: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = vault.token_program @ VaultError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CollectFees<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(address = vault.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault-token", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault-reserve", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump
+    )]
+    pub reward_reserve: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, address = vault.treasury @ VaultError::InvalidTreasury)]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = vault.token_program @ VaultError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct Borrow<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", borrow_vault.token_mint.as_ref(), borrow_vault.id_seed().as_ref()],
+        bump = borrow_vault.bump
+    )]
+    pub borrow_vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", collateral_vault.token_mint.as_ref(), collateral_vault.id_seed().as_ref()],
+        bump = collateral_vault.bump,
+        constraint = collateral_vault.key() != borrow_vault.key() @ VaultError::InvalidCollateralVault
+    )]
+    pub collateral_vault: Account<'info, Vault>,
+    
+    #[account(
+        seeds = [b"collateral-config", borrow_vault.key().as_ref(), collateral_vault.key().as_ref()],
+        bump = collateral_config.bump,
+        constraint = collateral_config.enabled @ VaultError::CollateralNotAccepted
+    )]
+    pub collateral_config: Account<'info, CollateralConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"user-position", collateral_vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: Account<'info, UserPosition>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 16 + 1,
+        seeds = [
+            b"borrow-position",
+            borrow_vault.key().as_ref(),
+            collateral_vault.key().as_ref(),
+            user.key().as_ref()
+        ],
+        bump
+    )]
+    pub borrow_position: Account<'info, BorrowPosition>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(address = borrow_vault.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key(),
+        constraint = user_token_account.mint == borrow_vault.token_mint
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault-token", borrow_vault.token_mint.as_ref(), borrow_vault.id_seed().as_ref()],
+        bump
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = borrow_vault.token_program @ VaultError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    
+    #[account(
+        constraint = collateral_config.price_feed == Some(collateral_price_feed.key()) @ VaultError::InvalidPriceFeed,
+        constraint = collateral_price_feed.authority == borrow_vault.authority @ VaultError::InvalidPriceFeed
+    )]
+    pub collateral_price_feed: Option<Account<'info, PriceFeed>>,
+    
+    #[account(
+        constraint = borrow_vault.price_feed == Some(borrow_price_feed.key()) @ VaultError::InvalidPriceFeed,
+        constraint = borrow_price_feed.authority == borrow_vault.authority @ VaultError::InvalidPriceFeed
+    )]
+    pub borrow_price_feed: Option<Account<'info, PriceFeed>>,
+
+}
+
+#[derive(Accounts)]
+pub struct Repay<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", borrow_vault.token_mint.as_ref(), borrow_vault.id_seed().as_ref()],
+        bump = borrow_vault.bump
+    )]
+    pub borrow_vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"borrow-position",
+            borrow_vault.key().as_ref(),
+            borrow_position.collateral_vault.as_ref(),
+            user.key().as_ref()
+        ],
+        bump = borrow_position.bump
+    )]
+    pub borrow_position: Account<'info, BorrowPosition>,
+    
+    pub user: Signer<'info>,
+    
+    #[account(address = borrow_vault.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key(),
+        constraint = user_token_account.mint == borrow_vault.token_mint
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault-token", borrow_vault.token_mint.as_ref(), borrow_vault.id_seed().as_ref()],
+        bump
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault-reserve", borrow_vault.token_mint.as_ref(), borrow_vault.id_seed().as_ref()],
+        bump
+    )]
+    pub reward_reserve: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = borrow_vault.token_program @ VaultError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseCollateral<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", borrow_vault.token_mint.as_ref(), borrow_vault.id_seed().as_ref()],
+        bump = borrow_vault.bump
+    )]
+    pub borrow_vault: Account<'info, Vault>,
+    
+    #[account(mut, address = borrow_position.collateral_vault)]
+    pub collateral_vault: Account<'info, Vault>,
+    
+    #[account(
+        seeds = [b"collateral-config", borrow_vault.key().as_ref(), collateral_vault.key().as_ref()],
+        bump = collateral_config.bump
+    )]
+    pub collateral_config: Account<'info, CollateralConfig>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"borrow-position",
+            borrow_vault.key().as_ref(),
+            collateral_vault.key().as_ref(),
+            user.key().as_ref()
+        ],
+        bump = borrow_position.bump
+    )]
+    pub borrow_position: Account<'info, BorrowPosition>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + (16 + 8) * MAX_REWARD_STREAMS,
+        seeds = [b"user-position", collateral_vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: Account<'info, UserPosition>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    #[account(
+        constraint = collateral_config.price_feed == Some(collateral_price_feed.key()) @ VaultError::InvalidPriceFeed,
+        constraint = collateral_price_feed.authority == borrow_vault.authority @ VaultError::InvalidPriceFeed
+    )]
+    pub collateral_price_feed: Option<Account<'info, PriceFeed>>,
+    
+    #[account(
+        constraint = borrow_vault.price_feed == Some(borrow_price_feed.key()) @ VaultError::InvalidPriceFeed,
+        constraint = borrow_price_feed.authority == borrow_vault.authority @ VaultError::InvalidPriceFeed
+    )]
+    pub borrow_price_feed: Option<Account<'info, PriceFeed>>,
+
+}
+
+#[derive(Accounts)]
+pub struct Liquidate<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", borrow_vault.token_mint.as_ref(), borrow_vault.id_seed().as_ref()],
+        bump = borrow_vault.bump
+    )]
+    pub borrow_vault: Account<'info, Vault>,
+    
+    #[account(mut, address = borrow_position.collateral_vault)]
+    pub collateral_vault: Account<'info, Vault>,
+    
+    #[account(
+        seeds = [b"collateral-config", borrow_vault.key().as_ref(), collateral_vault.key().as_ref()],
+        bump = collateral_config.bump
+    )]
+    pub collateral_config: Account<'info, CollateralConfig>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"borrow-position",
+            borrow_vault.key().as_ref(),
+            collateral_vault.key().as_ref(),
+            borrower.key().as_ref()
+        ],
+        bump = borrow_position.bump
+    )]
+    pub borrow_position: Account<'info, BorrowPosition>,
+    
+    /// CHECK: only used to derive the borrow position being liquidated
+    pub borrower: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = liquidator,
+        space = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + (16 + 8) * MAX_REWARD_STREAMS,
+        seeds = [b"user-position", collateral_vault.key().as_ref(), liquidator.key().as_ref()],
+        bump
+    )]
+    pub liquidator_position: Account<'info, UserPosition>,
+    
+    #[account(mut)]
+    pub liquidator: Signer<'info>,
+    
+    #[account(address = borrow_vault.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = liquidator_token_account.owner == liquidator.key(),
+        constraint = liquidator_token_account.mint == borrow_vault.token_mint
+    )]
+    pub liquidator_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault-token", borrow_vault.token_mint.as_ref(), borrow_vault.id_seed().as_ref()],
+        bump
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault-reserve", borrow_vault.token_mint.as_ref(), borrow_vault.id_seed().as_ref()],
+        bump
+    )]
+    pub reward_reserve: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = borrow_vault.token_program @ VaultError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    
+    #[account(
+        constraint = collateral_config.price_feed == Some(collateral_price_feed.key()) @ VaultError::InvalidPriceFeed,
+        constraint = collateral_price_feed.authority == borrow_vault.authority @ VaultError::InvalidPriceFeed
+    )]
+    pub collateral_price_feed: Option<Account<'info, PriceFeed>>,
+    
+    #[account(
+        constraint = borrow_vault.price_feed == Some(borrow_price_feed.key()) @ VaultError::InvalidPriceFeed,
+        constraint = borrow_price_feed.authority == borrow_vault.authority @ VaultError::InvalidPriceFeed
+    )]
+    pub borrow_price_feed: Option<Account<'info, PriceFeed>>,
+
+}
+
+#[derive(Accounts)]
+pub struct SetBorrowConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetCollateralConfig<'info> {
+    #[account(
+        seeds = [b"vault", borrow_vault.token_mint.as_ref(), borrow_vault.id_seed().as_ref()],
+        bump = borrow_vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub borrow_vault: Account<'info, Vault>,
+    
+    #[account(
+        seeds = [b"vault", collateral_vault.token_mint.as_ref(), collateral_vault.id_seed().as_ref()],
+        bump = collateral_vault.bump,
+        constraint = collateral_vault.key() != borrow_vault.key() @ VaultError::InvalidCollateralVault
+    )]
+    pub collateral_vault: Account<'info, Vault>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + 32 + 32 + (1 + 32) + 1 + 1,
+        seeds = [b"collateral-config", borrow_vault.key().as_ref(), collateral_vault.key().as_ref()],
+        bump
+    )]
+    pub collateral_config: Account<'info, CollateralConfig>,
+    
+    #[account(
+        constraint = price_feed.token_mint == collateral_vault.token_mint @ VaultError::InvalidPriceFeed,
+        constraint = price_feed.authority == authority.key() @ VaultError::InvalidPriceFeed
+    )]
+    pub price_feed: Option<Account<'info, PriceFeed>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPriceFeed<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        constraint = price_feed.token_mint == vault.token_mint @ VaultError::InvalidPriceFeed,
+        constraint = price_feed.authority == authority.key() @ VaultError::InvalidPriceFeed
+    )]
+    pub price_feed: Option<Account<'info, PriceFeed>>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializePriceFeed<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 32 + 1 + 8 + 8 + 1,
+        seeds = [b"price-feed", token_mint.key().as_ref(), authority.key().as_ref()],
+        bump
+    )]
+    pub price_feed: Account<'info, PriceFeed>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePrice<'info> {
+    #[account(
+        mut,
+        seeds = [b"price-feed", price_feed.token_mint.as_ref(), authority.key().as_ref()],
+        bump = price_feed.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub price_feed: Account<'info, PriceFeed>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(index: u8)]
+pub struct AddRewardStream<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + 32 + 1 + 32 + 32 + 32 + 8 + 8 + 8 + 16 + 8 + 8 + 1,
+        seeds = [b"reward-stream", vault.key().as_ref(), &[index]],
+        bump
+    )]
+    pub reward_stream: Account<'info, RewardStream>,
+    
+    #[account(mint::token_program = reward_token_program)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"reward-escrow", reward_stream.key().as_ref()],
+        bump,
+        token::mint = reward_mint,
+        token::authority = reward_stream,
+        token::token_program = reward_token_program
+    )]
+    pub reward_escrow: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = authority_reward_account.owner == authority.key(),
+        constraint = authority_reward_account.mint == reward_mint.key()
+    )]
+    pub authority_reward_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub reward_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ExtendRewardStream<'info> {
+    #[account(
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        seeds = [b"reward-stream", vault.key().as_ref(), &[reward_stream.index]],
+        bump = reward_stream.bump,
+        has_one = reward_mint,
+        has_one = reward_escrow,
+        constraint = vault.reward_streams.get(reward_stream.index as usize) == Some(&Some(reward_stream.key()))
+            @ VaultError::InvalidRewardStream
+    )]
+    pub reward_stream: Account<'info, RewardStream>,
+    
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(mut)]
+    pub reward_escrow: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = authority_reward_account.owner == authority.key(),
+        constraint = authority_reward_account.mint == reward_mint.key()
+    )]
+    pub authority_reward_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+    
+    #[account(address = reward_stream.reward_token_program @ VaultError::InvalidTokenProgram)]
+    pub reward_token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct SweepRewardStream<'info> {
+    #[account(
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        seeds = [b"reward-stream", vault.key().as_ref(), &[reward_stream.index]],
+        bump = reward_stream.bump,
+        has_one = reward_mint,
+        has_one = reward_escrow,
+        constraint = vault.reward_streams.get(reward_stream.index as usize) == Some(&Some(reward_stream.key()))
+            @ VaultError::InvalidRewardStream
+    )]
+    pub reward_stream: Account<'info, RewardStream>,
+    
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(mut)]
+    pub reward_escrow: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = authority_reward_account.owner == authority.key(),
+        constraint = authority_reward_account.mint == reward_mint.key()
+    )]
+    pub authority_reward_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+    
+    #[account(address = reward_stream.reward_token_program @ VaultError::InvalidTokenProgram)]
+    pub reward_token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CloseRewardStream<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        seeds = [b"reward-stream", vault.key().as_ref(), &[reward_stream.index]],
+        bump = reward_stream.bump,
+        has_one = reward_mint,
+        has_one = reward_escrow,
+        constraint = vault.reward_streams.get(reward_stream.index as usize) == Some(&Some(reward_stream.key()))
+            @ VaultError::InvalidRewardStream
+    )]
+    pub reward_stream: Account<'info, RewardStream>,
+    
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(mut)]
+    pub reward_escrow: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = authority_reward_account.owner == authority.key(),
+        constraint = authority_reward_account.mint == reward_mint.key()
+    )]
+    pub authority_reward_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(address = reward_stream.reward_token_program @ VaultError::InvalidTokenProgram)]
+    pub reward_token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        seeds = [b"user-position", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: Account<'info, UserPosition>,
+    
+    #[account(
+        mut,
+        seeds = [b"reward-stream", vault.key().as_ref(), &[reward_stream.index]],
+        bump = reward_stream.bump
+    )]
+    pub reward_stream: Account<'info, RewardStream>,
+    
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(mut)]
+    pub reward_escrow: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user_reward_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub user: Signer<'info>,
+    
+    pub reward_token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimAllRewards<'info> {
+    #[account(
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        seeds = [b"user-position", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: Account<'info, UserPosition>,
+    
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FlashBorrow<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    pub borrower: Signer<'info>,
+    
+    #[account(address = vault.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = borrower_token_account.mint == vault.token_mint
+    )]
+    pub borrower_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault-token", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: the instructions sysvar, checked by address
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    #[account(address = vault.token_program @ VaultError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct FlashRepay<'info> {
+    // Must stay the first account; `flash_borrow` looks for it there
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    pub payer: Signer<'info>,
+    
+    #[account(address = vault.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = payer_token_account.owner == payer.key(),
+        constraint = payer_token_account.mint == vault.token_mint
+    )]
+    pub payer_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault-token", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault-reserve", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump
+    )]
+    pub reward_reserve: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = vault.token_program @ VaultError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct AddStrategy<'info> {
+    #[account(
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 1,
+        seeds = [b"strategy", vault.key().as_ref(), strategy_program.key().as_ref()],
+        bump
+    )]
+    pub strategy: Account<'info, Strategy>,
+    
+    /// CHECK: any executable program; it is only ever invoked through the strategy interface
+    #[account(executable)]
+    pub strategy_program: UncheckedAccount<'info>,
+    
+    #[account(
+        constraint = deposit_account.mint == vault.token_mint @ VaultError::InvalidDepositAccount,
+        constraint = deposit_account.owner != vault.key() @ VaultError::InvalidDepositAccount
+    )]
+    pub deposit_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetAllocationCap<'info> {
+    #[account(
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        seeds = [b"strategy", vault.key().as_ref(), strategy.strategy_program.as_ref()],
+        bump = strategy.bump
+    )]
+    pub strategy: Account<'info, Strategy>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetLossThreshold<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReportStrategy<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        seeds = [b"strategy", vault.key().as_ref(), strategy.strategy_program.as_ref()],
+        bump = strategy.bump
+    )]
+    pub strategy: Account<'info, Strategy>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct StrategyOperation<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        seeds = [b"strategy", vault.key().as_ref(), strategy_program.key().as_ref()],
+        bump = strategy.bump
+    )]
+    pub strategy: Account<'info, Strategy>,
+    
+    /// CHECK: the registered program, pinned by the strategy's seeds
+    #[account(executable)]
+    pub strategy_program: UncheckedAccount<'info>,
+    
+    #[account(mut, address = strategy.deposit_account @ VaultError::InvalidDepositAccount)]
+    pub deposit_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+    
+    #[account(address = vault.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault-token", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault-reserve", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump
+    )]
+    pub reward_reserve: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = vault.token_program @ VaultError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct BeginSunset<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseVault<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"vault", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(address = vault.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = authority_token_account.owner == authority.key(),
+        constraint = authority_token_account.mint == vault.token_mint
+    )]
+    pub authority_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault-token", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault-reserve", vault.token_mint.as_ref(), vault.id_seed().as_ref()],
+        bump
+    )]
+    pub reward_reserve: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = vault.token_program @ VaultError::InvalidTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct GetUserBalance<'info> {
+    pub vault: Account<'info, Vault>,
+    pub user_position: Account<'info, UserPosition>,
+}
+
+#[derive(Accounts)]
+pub struct VaultView<'info> {
+    pub vault: Account<'info, Vault>,
+}
+
+#[account]
+pub struct Vault {
+    pub authority: Pubkey,
+    pub pending_authority: Option<Pubkey>,
+    pub token_mint: Pubkey,
+    pub vault_id: u64,
+    pub token_vault: Pubkey,
+    pub token_program: Pubkey, // SPL Token or Token-2022, fixed at initialization
+    pub reward_reserve: Pubkey,
+    pub treasury: Pubkey, // receives protocol fees and early-withdrawal penalties
+    pub interest_rate: u64, // in basis points; the rate in force until the next state change
+    pub interest_model: InterestModel,
+    pub rate_curve: Option<RateCurve>, // derives `interest_rate` from utilization when set
+    pub total_borrowed: u64, // principal lent out of the vault token account
+    pub total_collateral_shares: u64, // this vault's shares escrowed in borrow positions elsewhere
+    pub borrow_rate: u64, // in basis points, what borrowers currently pay
+    pub borrow_index: u128, // WAD-scaled growth of one unit of debt since initialization
+    pub borrow_index_updated_at: i64,
+    pub borrow_config: BorrowConfig,
+    pub price_feed: Option<Pubkey>, // values this vault's token when it is lent against
+    pub reward_streams: [Option<Pubkey>; MAX_REWARD_STREAMS], // indexed by `RewardStream.index`
+    pub flash_loan_amount: u64, // outstanding within the current transaction, 0 otherwise
+    pub flash_loan_fee: u64, // owed on top of `flash_loan_amount`
+    pub total_allocated: u64, // principal deployed to strategies
+    pub loss_threshold_bps: Option<u16>, // share of total assets one reported loss may take before deposits pause
+    pub min_deposit: u64,
+    pub fees: FeeConfig,
+    pub max_total_deposits: Option<u64>, // cap on principal across shares and locks
+    pub max_per_user: Option<u64>, // cap on a single position's principal
+    pub withdrawal_cooldown: i64, // seconds between `request_withdraw` and `claim_withdraw`; 0 is instant
+    pub guardian: Option<Pubkey>, // may only tighten safety limits
+    pub outflow_limit: OutflowLimit,
+    pub outflow_window_start: i64,
+    pub outflow_window_amount: u64, // paid out since `outflow_window_start`
+    pub outflow_window_tvl: u64, // TVL when the current window started
+    pub paused_flags: u8, // PAUSE_DEPOSITS | PAUSE_WITHDRAWALS
+    pub total_deposited: u64, // principal held in the vault token account
+    pub total_shares: u64,
+    pub total_accrued_interest: u64, // interest owed to depositors, paid from the reserve
+    pub last_accrual_time: i64,
+    pub total_locked: u64, // principal in lock sub-positions, outside the share pool
+    pub total_locked_interest: u64, // fixed interest promised to open locks, owed from the reserve
+    pub accrued_vault_fees: u64, // uncollected fees held in the vault token account
+    pub accrued_reserve_fees: u64, // uncollected fees still owed from the reward reserve
+    pub pending_principal: u64, // requested withdrawals awaiting claim, held in the vault token account
+    pub pending_interest: u64, // requested withdrawals awaiting claim, owed from the reward reserve
+    pub lock_terms: [LockTerm; MAX_LOCK_TERMS],
+    pub latest_unlock_time: i64, // furthest unlock time of any lock opened so far
+    pub sunset_deadline: Option<i64>, // set by `begin_sunset`; `close_vault` may run after it
+    pub bump: u8,
+    pub created_at: i64,
+}
+
+impl Vault {
+    /// Trailing PDA seed for this vault; see `vault_id_seed`
+    pub fn id_seed(&self) -> Vec<u8> {
+        vault_id_seed(self.vault_id)
+    }
+
+    /// Whether any of the given `PAUSE_*` flags are set
+    pub fn is_paused(&self, flags: u8) -> bool {
+        self.paused_flags & flags != 0
+    }
+
+    /// Configured lock term for `term_id`
+    pub fn lock_term(&self, term_id: u8) -> Result<LockTerm> {
+        let term = self
+            .lock_terms
+            .get(term_id as usize)
+            .copied()
+            .ok_or(VaultError::InvalidLockTerm)?;
+        require!(term.duration > 0, VaultError::InvalidLockTerm);
+        Ok(term)
+    }
+
+    /// Check that taking `amount` more principal keeps the vault under `max_total_deposits`
+    pub fn check_deposit_cap(&self, amount: u64) -> Result<()> {
+        if let Some(max_total_deposits) = self.max_total_deposits {
+            let total = self.total_deposited.safe_add(self.total_locked)?.safe_add(amount)?;
+            require!(total <= max_total_deposits, VaultError::DepositCapExceeded);
+        }
+        Ok(())
+    }
+
+    /// Check that `amount` more principal keeps `position` under `max_per_user`, counting
+    /// its locks as well as its shares
+    pub fn check_user_cap(&self, position: &UserPosition, amount: u64) -> Result<()> {
+        if let Some(max_per_user) = self.max_per_user {
+            let total = position
+                .deposited_amount
+                .safe_add(position.locked_amount)?
+                .safe_add(amount)?;
+            require!(total <= max_per_user, VaultError::UserDepositCapExceeded);
+        }
+        Ok(())
+    }
+
+    /// Everything the vault holds for users: the share pool, locks and pending withdrawals
+    pub fn tvl(&self) -> Result<u64> {
+        self.total_assets()?
+            .safe_add(self.total_locked)?
+            .safe_add(self.pending_principal)?
+            .safe_add(self.pending_interest)
+    }
+
+    /// Count `amount` against the outflow limit, starting a new window once the current
+    /// one has ended
+    pub fn record_outflow(&mut self, amount: u64, current_time: i64) -> Result<()> {
+        if !self.outflow_limit.is_enabled() {
+            return Ok(());
+        }
+        
+        if current_time >= self.outflow_window_start.safe_add(self.outflow_limit.window)? {
+            self.outflow_window_start = current_time;
+            self.outflow_window_amount = 0;
+            self.outflow_window_tvl = self.tvl()?;
+        }
+        
+        let window_amount = self.outflow_window_amount.safe_add(amount)?;
+        if let Some(allowance) = self.outflow_limit.allowance(self.outflow_window_tvl)? {
+            require!(window_amount <= allowance, VaultError::OutflowLimitExceeded);
+        }
+        self.outflow_window_amount = window_amount;
+        Ok(())
+    }
+
+    /// Reward reserve balance left for interest once pending withdrawals and the interest
+    /// promised to open locks are set aside
+    pub fn available_reserve(&self, reserve_balance: u64) -> u64 {
+        reserve_balance
+            .saturating_sub(self.pending_interest)
+            .saturating_sub(self.total_locked_interest)
+    }
+
+    /// Burn the shares behind `amount` from `position` and split the payout between the
+    /// reward reserve and the vault token account, net of the exit fee.
+    ///
+    /// Interest is paid out first, and always from the reward reserve so that principal
+    /// held in the vault token account stays fully backed. Principal can never exceed what
+    /// the vault holds, even once fees have eaten into it.
+    pub fn redeem(&mut self, position: &mut UserPosition, amount: u64) -> Result<Redemption> {
+        // Total available balance is the current value of the position's shares
+        let total_available = self.convert_to_assets(position.shares)?;
+        require!(amount <= total_available, VaultError::InsufficientBalance);
+        
+        let shares = self.preview_withdraw(amount)?;
+        
+        let position_interest = total_available.saturating_sub(position.deposited_amount);
+        let interest_amount = amount
+            .min(position_interest)
+            .max(amount.saturating_sub(self.total_deposited))
+            .min(self.total_accrued_interest);
+        let principal_amount = amount.safe_sub(interest_amount)?;
+        require!(principal_amount <= self.available_liquidity(), VaultError::InsufficientLiquidity);
+        
+        // The exit fee is withheld from the interest leg first, and stays behind in
+        // whichever account that leg would have been paid from
+        let fee = bps_of(amount, self.fees.exit_fee_bps)?;
+        let reserve_fee = fee.min(interest_amount);
+        let vault_fee = fee.safe_sub(reserve_fee)?;
+        
+        position.shares = position.shares.safe_sub(shares)?;
+        position.deposited_amount = position.deposited_amount.safe_sub(principal_amount)?;
+        if position.shares == 0 {
+            // Any basis left over is rounding dust that stays with the vault
+            position.deposited_amount = 0;
+        }
+        
+        self.total_shares = self.total_shares.safe_sub(shares)?;
+        self.total_accrued_interest = self.total_accrued_interest.safe_sub(interest_amount)?;
+        self.total_deposited = self.total_deposited.safe_sub(principal_amount)?;
+        self.accrued_reserve_fees = self.accrued_reserve_fees.safe_add(reserve_fee)?;
+        self.accrued_vault_fees = self.accrued_vault_fees.safe_add(vault_fee)?;
+        
+        Ok(Redemption {
+            shares,
+            interest_amount,
+            principal_amount,
+            fee,
+            interest_transfer: interest_amount.safe_sub(reserve_fee)?,
+            principal_transfer: principal_amount.safe_sub(vault_fee)?,
+        })
+    }
+
+    /// Shares that earn reward stream emissions. Shares escrowed as collateral sit in no
+    /// position that could claim them, so they earn none and leave more for the rest.
+    pub fn reward_shares(&self) -> Result<u64> {
+        self.total_shares.safe_sub(self.total_collateral_shares)
+    }
+
+    /// Total assets managed on behalf of share holders
+    pub fn total_assets(&self) -> Result<u64> {
+        self.total_deposited.safe_add(self.total_accrued_interest)
+    }
+
+    /// Share of total assets currently lent out, in basis points
+    pub fn utilization_bps(&self) -> Result<u64> {
+        let total_assets = self.total_assets()?;
+        if total_assets == 0 {
+            return Ok(0);
+        }
+        let utilization = (self.total_borrowed as u128)
+            .safe_mul(interest::BPS_DENOMINATOR)?
+            .safe_div(total_assets as u128)?;
+        Ok(to_u64(utilization)?.min(interest::BPS_DENOMINATOR as u64))
+    }
+
+    /// Principal in the vault token account that is neither lent out nor deployed to a strategy
+    pub fn available_liquidity(&self) -> u64 {
+        self.total_deposited
+            .saturating_sub(self.total_borrowed)
+            .saturating_sub(self.total_allocated)
+    }
+
+    /// Recompute the borrow and deposit rates from the rate curve, if any. Call after every
+    /// change to the pool so the next accrual uses the rates that held over its interval.
+    ///
+    /// Under a curve depositors earn the borrow rate scaled by utilization, which is what
+    /// borrowers pay in; without one both sides use the flat `interest_rate`.
+    pub fn refresh_rate(&mut self) -> Result<()> {
+        match self.rate_curve {
+            Some(curve) => {
+                let utilization = self.utilization_bps()?;
+                self.borrow_rate = curve.rate(utilization)?;
+                self.interest_rate = self
+                    .borrow_rate
+                    .safe_mul(utilization)?
+                    .safe_div(interest::BPS_DENOMINATOR as u64)?;
+            }
+            None => self.borrow_rate = self.interest_rate,
+        }
+        Ok(())
+    }
+
+    /// Accrue vault-wide interest up to `current_time` under the vault's interest model,
+    /// net of performance and management fees, which are returned for reporting.
+    ///
+    /// Management fees come out of accrued interest first and principal after that, so
+    /// they keep accruing even when the vault earns nothing.
+    pub fn accrue_interest(&mut self, current_time: i64) -> Result<AccruedFees> {
+        self.borrow_index = interest::grow_index(
+            self.borrow_index,
+            self.borrow_rate,
+            current_time.safe_sub(self.borrow_index_updated_at)?,
+        )?;
+        self.borrow_index_updated_at = self.borrow_index_updated_at.max(current_time);
+        
+        let time_elapsed = current_time.safe_sub(self.last_accrual_time)?.max(0);
+        let interest = self.interest_model.accrue(
+            self.total_deposited,
+            self.total_assets()?,
+            self.interest_rate,
+            time_elapsed,
+        )?;
+        
+        let performance_fee = bps_of(interest, self.fees.performance_fee_bps)?;
+        self.total_accrued_interest = self
+            .total_accrued_interest
+            .safe_add(interest.safe_sub(performance_fee)?)?;
+        self.accrued_reserve_fees = self.accrued_reserve_fees.safe_add(performance_fee)?;
+        
+        let management_fee = interest::calculate_interest(
+            self.total_assets()?,
+            self.fees.management_fee_bps as u64,
+            time_elapsed,
+        )?;
+        let from_interest = management_fee.min(self.total_accrued_interest);
+        let from_principal = management_fee.safe_sub(from_interest)?;
+        self.total_accrued_interest = self.total_accrued_interest.safe_sub(from_interest)?;
+        self.accrued_reserve_fees = self.accrued_reserve_fees.safe_add(from_interest)?;
+        self.total_deposited = self.total_deposited.safe_sub(from_principal)?;
+        self.accrued_vault_fees = self.accrued_vault_fees.safe_add(from_principal)?;
+        
+        self.last_accrual_time = self.last_accrual_time.safe_add(time_elapsed)?;
+        Ok(AccruedFees {
+            performance_fee,
+            management_fee,
+        })
+    }
+
+    /// Copy of the vault with interest accrued up to `current_time`, for read-only views
+    pub fn projected(&self, current_time: i64) -> Result<Vault> {
+        let mut vault = self.clone();
+        vault.accrue_interest(current_time)?;
+        Ok(vault)
+    }
+
+    /// Shares minted for `assets`, rounded down in favour of the vault.
+    ///
+    /// Fails once losses have wiped out every asset behind existing shares, since a new
+    /// deposit would otherwise be shared with holders who no longer have any claim.
+    pub fn convert_to_shares(&self, assets: u64) -> Result<u64> {
+        let total_assets = self.total_assets()?;
+        if self.total_shares == 0 {
+            return Ok(assets);
+        }
+        require!(total_assets > 0, VaultError::VaultInsolvent);
+        to_u64(
+            (assets as u128)
+                .safe_mul(self.total_shares as u128)?
+                .safe_div(total_assets as u128)?,
+        )
+    }
+
+    /// Assets redeemable for `shares`, rounded down in favour of the vault
+    pub fn convert_to_assets(&self, shares: u64) -> Result<u64> {
+        if self.total_shares == 0 {
+            return Ok(shares);
+        }
+        to_u64(
+            (shares as u128)
+                .safe_mul(self.total_assets()? as u128)?
+                .safe_div(self.total_shares as u128)?,
+        )
+    }
+
+    /// Shares burned to withdraw `assets`, rounded up in favour of the vault
+    pub fn preview_withdraw(&self, assets: u64) -> Result<u64> {
+        let total_assets = self.total_assets()?;
+        if self.total_shares == 0 || total_assets == 0 {
+            return Ok(assets);
+        }
+        let numerator = (assets as u128).safe_mul(self.total_shares as u128)?;
+        to_u64(numerator.div_ceil(total_assets as u128))
+    }
+}
+
+/// Outcome of `Vault::redeem`
+pub struct Redemption {
+    pub shares: u64, // burned from the position
+    pub interest_amount: u64, // gross, before the exit fee
+    pub principal_amount: u64, // gross, before the exit fee
+    pub fee: u64,
+    pub interest_transfer: u64, // owed to the user from the reward reserve
+    pub principal_transfer: u64, // owed to the user from the vault token account
+}
+
+/// Fees charged by a single `Vault::accrue_interest` call
+pub struct AccruedFees {
+    pub performance_fee: u64,
+    pub management_fee: u64,
+}
+
+/// Accrue interest on a live vault and emit an event for each fee it charged
+fn accrue_vault(vault: &mut Account<Vault>, current_time: i64) -> Result<()> {
+    let fees = vault.accrue_interest(current_time)?;
+    vault.refresh_rate()?;
+    for (kind, amount) in [
+        (FeeKind::Performance, fees.performance_fee),
+        (FeeKind::Management, fees.management_fee),
+    ] {
+        if amount > 0 {
+            emit!(FeeChargedEvent {
+                vault: vault.key(),
+                user: None,
+                kind,
+                amount,
+                timestamp: current_time,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Settle `position` against every stream of `vault` before its shares change. The
+/// streams come in through the remaining accounts in slot order; leaving one out would
+/// credit its rewards at a stale index, so all of them are required.
+fn settle_rewards(
+    vault: &Vault,
+    streams: &[AccountInfo],
+    position: &mut UserPosition,
+    current_time: i64,
+) -> Result<()> {
+    let mut streams = streams.iter();
+    for stream_key in vault.reward_streams.iter().flatten() {
+        let stream_info = streams.next().ok_or(VaultError::InvalidRewardStream)?;
+        require_keys_eq!(stream_info.key(), *stream_key, VaultError::InvalidRewardStream);
+        require!(stream_info.is_writable, VaultError::InvalidRewardStream);
+        
+        let mut stream = RewardStream::try_deserialize(&mut &stream_info.try_borrow_data()?[..])?;
+        stream.update(vault.reward_shares()?, current_time)?;
+        position.settle_rewards(stream.index, stream.reward_per_share)?;
+        stream.try_serialize(&mut &mut stream_info.try_borrow_mut_data()?[..])?;
+    }
+    Ok(())
+}
+
+/// Invoke a strategy instruction with the `Strategy` account signing, forwarding `remaining`
+/// as the strategy's own accounts, then reload both vault token accounts.
+///
+/// The `Strategy` PDA owns nothing and has no authority over the vault's token accounts, so
+/// its signature only proves which vault is calling. The strategy can send tokens to the
+/// vault but never take them.
+#[allow(clippy::too_many_arguments)]
+fn invoke_strategy<'info>(
+    strategy: &Account<'info, Strategy>,
+    strategy_program: &UncheckedAccount<'info>,
+    name: &str,
+    amount: Option<u64>,
+    token_mint: &InterfaceAccount<'info, Mint>,
+    vault_token_account: &mut InterfaceAccount<'info, TokenAccount>,
+    reward_reserve: &mut InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    remaining: &[AccountInfo<'info>],
+) -> Result<()> {
+    let seeds = &[
+        b"strategy",
+        strategy.vault.as_ref(),
+        strategy.strategy_program.as_ref(),
+        &[strategy.bump],
+    ];
+    strategy::invoke(
+        &strategy_program.to_account_info(),
+        name,
+        amount,
+        [
+            &strategy.to_account_info(),
+            &token_mint.to_account_info(),
+            &vault_token_account.to_account_info(),
+            &reward_reserve.to_account_info(),
+            &token_program.to_account_info(),
+        ],
+        remaining,
+        &[&seeds[..]],
+    )?;
+    
+    vault_token_account.reload()?;
+    reward_reserve.reload()?;
+    Ok(())
+}
+
+/// Escrow `amount` reward tokens for a stream, refusing if the escrow receives less
+fn fund_reward_escrow<'info>(
+    authority: &Signer<'info>,
+    authority_reward_account: &InterfaceAccount<'info, TokenAccount>,
+    reward_mint: &InterfaceAccount<'info, Mint>,
+    reward_escrow: &mut InterfaceAccount<'info, TokenAccount>,
+    reward_token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    let balance_before = reward_escrow.amount;
+    let cpi_accounts = TransferChecked {
+        from: authority_reward_account.to_account_info(),
+        mint: reward_mint.to_account_info(),
+        to: reward_escrow.to_account_info(),
+        authority: authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(reward_token_program.to_account_info(), cpi_accounts);
+    token_interface::transfer_checked(cpi_ctx, amount, reward_mint.decimals)?;
+    
+    // Emission is promised in full, so the escrow must receive all of it
+    reward_escrow.reload()?;
+    require!(
+        reward_escrow.amount.safe_sub(balance_before)? >= amount,
+        VaultError::InvalidRewardStream
+    );
+    Ok(())
+}
+
+/// Settle `position` against `stream` and pay everything pending in it from the stream's
+/// escrow to `user_reward_account`, which must belong to `user`. Returns the amount paid.
+#[allow(clippy::too_many_arguments)]
+fn claim_from_stream<'info>(
+    vault: &Account<'info, Vault>,
+    stream: &mut RewardStream,
+    stream_info: AccountInfo<'info>,
+    position: &mut UserPosition,
+    reward_mint: AccountInfo<'info>,
+    decimals: u8,
+    reward_escrow: AccountInfo<'info>,
+    user_reward_account: AccountInfo<'info>,
+    reward_token_program: AccountInfo<'info>,
+    user: Pubkey,
+    current_time: i64,
+) -> Result<u64> {
+    require_keys_eq!(reward_mint.key(), stream.reward_mint, VaultError::InvalidRewardStream);
+    require_keys_eq!(reward_escrow.key(), stream.reward_escrow, VaultError::InvalidRewardStream);
+    require_keys_eq!(
+        reward_token_program.key(),
+        stream.reward_token_program,
+        VaultError::InvalidTokenProgram
+    );
+    let destination = TokenAccount::try_deserialize(&mut &user_reward_account.try_borrow_data()?[..])?;
+    require!(
+        destination.owner == user && destination.mint == stream.reward_mint,
+        VaultError::InvalidRewardStream
+    );
+    
+    stream.update(vault.reward_shares()?, current_time)?;
+    position.settle_rewards(stream.index, stream.reward_per_share)?;
+    
+    let amount = position.pending_rewards[stream.index as usize];
+    if amount == 0 {
+        return Ok(0);
+    }
+    position.pending_rewards[stream.index as usize] = 0;
+    stream.unclaimed_rewards = stream.unclaimed_rewards.safe_sub(amount)?;
+    
+    let vault_key = vault.key();
+    let seeds = &[b"reward-stream", vault_key.as_ref(), &[stream.index], &[stream.bump]];
+    let signer = &[&seeds[..]];
+    
+    let stream_key = stream_info.key();
+    let cpi_accounts = TransferChecked {
+        from: reward_escrow,
+        mint: reward_mint,
+        to: user_reward_account,
+        authority: stream_info,
+    };
+    let cpi_ctx = CpiContext::new_with_signer(reward_token_program, cpi_accounts, signer);
+    token_interface::transfer_checked(cpi_ctx, amount, decimals)?;
     
-    interest as u64
+    emit!(RewardsClaimedEvent {
+        user,
+        vault: vault_key,
+        reward_stream: stream_key,
+        amount,
+        timestamp: current_time,
+    });
+    Ok(amount)
+}
+
+/// Value of `shares` of `collateral_vault`, accrued to `current_time`, in `borrow_vault`'s
+/// token. The same mint is worth 1:1; otherwise the collateral config's price feed and the
+/// borrow vault's must be passed in.
+fn collateral_value(
+    collateral_vault: &Vault,
+    borrow_vault: &Vault,
+    shares: u64,
+    collateral_price_feed: Option<&PriceFeed>,
+    borrow_price_feed: Option<&PriceFeed>,
+    current_time: i64,
+) -> Result<u64> {
+    let assets = collateral_vault.projected(current_time)?.convert_to_assets(shares)?;
+    if collateral_vault.token_mint == borrow_vault.token_mint {
+        return Ok(assets);
+    }
+    let (collateral_feed, borrow_feed) = collateral_price_feed
+        .zip(borrow_price_feed)
+        .ok_or(VaultError::MissingPriceFeed)?;
+    lending::convert_at_prices(
+        assets,
+        collateral_feed.fresh_price(current_time)?,
+        collateral_feed.decimals,
+        borrow_feed.fresh_price(current_time)?,
+        borrow_feed.decimals,
+    )
+}
+
+/// Pay `amount` of `position`'s debt, interest into the reward reserve first and the rest
+/// to the vault token account. Only what arrives is credited, since transfer-fee mints
+/// withhold part of each leg. Returns the interest and principal repaid.
+#[allow(clippy::too_many_arguments)]
+fn repay_debt<'info>(
+    vault: &mut Vault,
+    position: &mut BorrowPosition,
+    amount: u64,
+    payer: &Signer<'info>,
+    payer_token_account: &InterfaceAccount<'info, TokenAccount>,
+    token_mint: &InterfaceAccount<'info, Mint>,
+    vault_token_account: &mut InterfaceAccount<'info, TokenAccount>,
+    reward_reserve: &mut InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<(u64, u64)> {
+    let interest_owed = position.debt.safe_sub(position.principal)?;
+    let interest_amount = amount.min(interest_owed);
+    let principal_amount = amount.safe_sub(interest_amount)?;
+    
+    let reserve_before = reward_reserve.amount;
+    let vault_before = vault_token_account.amount;
+    
+    for (to, leg) in [
+        (reward_reserve.to_account_info(), interest_amount),
+        (vault_token_account.to_account_info(), principal_amount),
+    ] {
+        if leg > 0 {
+            let cpi_accounts = TransferChecked {
+                from: payer_token_account.to_account_info(),
+                mint: token_mint.to_account_info(),
+                to,
+                authority: payer.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+            token_interface::transfer_checked(cpi_ctx, leg, token_mint.decimals)?;
+        }
+    }
+    
+    reward_reserve.reload()?;
+    vault_token_account.reload()?;
+    let interest_repaid = reward_reserve.amount.safe_sub(reserve_before)?;
+    let principal_repaid = vault_token_account.amount.safe_sub(vault_before)?;
+    
+    position.debt = position.debt.safe_sub(interest_repaid.safe_add(principal_repaid)?)?;
+    position.principal = position.principal.safe_sub(principal_repaid)?;
+    vault.total_borrowed = vault.total_borrowed.safe_sub(principal_repaid)?;
+    
+    Ok((interest_repaid, principal_repaid))
+}
+
+#[account]
+pub struct UserPosition {
+    pub owner: Pubkey,
+    pub vault: Pubkey,
+    pub shares: u64,
+    pub deposited_amount: u64, // principal cost basis of the shares
+    pub last_update_time: i64,
+    pub deposit_count: u64,
+    pub withdraw_count: u64,
+    pub lock_count: u64, // lock sub-positions opened, used to derive the next `LockPosition`
+    pub open_locks: u64, // lock sub-positions not yet withdrawn
+    pub locked_amount: u64, // principal across open lock sub-positions
+    pub reward_per_share_paid: [u128; MAX_REWARD_STREAMS], // per stream, index last settled at
+    pub pending_rewards: [u64; MAX_REWARD_STREAMS], // per stream, settled but not yet claimed
+}
+
+impl UserPosition {
+    /// Bind a position loaded through `init_if_needed` to its owner and vault.
+    ///
+    /// A new account, or one re-created after `close_position`, is all zeroes and is
+    /// claimed here; an existing one must already belong to `owner` in `vault`.
+    pub fn bind(&mut self, owner: Pubkey, vault: Pubkey) -> Result<()> {
+        if self.owner == Pubkey::default() {
+            self.owner = owner;
+            self.vault = vault;
+        }
+        require_keys_eq!(self.owner, owner, VaultError::InvalidPosition);
+        require_keys_eq!(self.vault, vault, VaultError::InvalidPosition);
+        Ok(())
+    }
+
+    /// Credit rewards earned in stream `index` by the current shares since the last settlement
+    pub fn settle_rewards(&mut self, index: u8, reward_per_share: u128) -> Result<()> {
+        let index = index as usize;
+        let earned = (self.shares as u128)
+            .safe_mul(reward_per_share.safe_sub(self.reward_per_share_paid[index])?)?
+            .safe_div(WAD)?;
+        self.pending_rewards[index] = self.pending_rewards[index].safe_add(to_u64(earned)?)?;
+        self.reward_per_share_paid[index] = reward_per_share;
+        Ok(())
+    }
+}
+
+/// Secondary token emitted to a vault's shareholders at `emission_rate` per second
+/// between `start_time` and `end_time`, paid out of `reward_escrow`
+#[account]
+pub struct RewardStream {
+    pub vault: Pubkey,
+    pub index: u8, // slot in `Vault.reward_streams`
+    pub reward_mint: Pubkey,
+    pub reward_escrow: Pubkey,
+    pub reward_token_program: Pubkey,
+    pub emission_rate: u64, // reward tokens per second
+    pub start_time: i64,
+    pub end_time: i64,
+    pub reward_per_share: u128, // WAD-scaled rewards accumulated per vault share
+    pub unclaimed_rewards: u64, // emitted to shareholders and still held in the escrow for them
+    pub last_update_time: i64,
+    pub bump: u8,
+}
+
+impl RewardStream {
+    /// Accumulate emissions since the last update across `total_shares`, the vault's
+    /// `reward_shares`. Emissions while there are none stay in the escrow until
+    /// `sweep_reward_stream`.
+    pub fn update(&mut self, total_shares: u64, current_time: i64) -> Result<()> {
+        let from = self.last_update_time.max(self.start_time);
+        let to = current_time.min(self.end_time);
+        if to > from && total_shares > 0 {
+            let emitted = (self.emission_rate as u128).safe_mul(to.safe_sub(from)? as u128)?;
+            self.reward_per_share = self
+                .reward_per_share
+                .safe_add(emitted.safe_mul(WAD)?.safe_div(total_shares as u128)?)?;
+            self.unclaimed_rewards = self.unclaimed_rewards.safe_add(to_u64(emitted)?)?;
+        }
+        self.last_update_time = self.last_update_time.max(current_time);
+        Ok(())
+    }
+}
+
+/// External program a vault deploys idle principal into, up to `allocation_cap`
+#[account]
+pub struct Strategy {
+    pub vault: Pubkey,
+    pub strategy_program: Pubkey,
+    pub deposit_account: Pubkey, // the strategy's token account that `allocate` transfers into
+    pub allocation_cap: u64,
+    pub allocated: u64, // principal currently held by the strategy
+    pub total_harvested: u64, // yield sent to the reward reserve over the strategy's life
+    pub bump: u8,
+}
+
+/// A time-locked deposit with its own rate and maturity, held outside the share pool
+#[account]
+pub struct LockPosition {
+    pub owner: Pubkey,
+    pub vault: Pubkey,
+    pub lock_id: u64,
+    pub term_id: u8,
+    pub amount: u64,
+    pub interest_rate: u64, // in basis points, fixed at deposit time
+    pub early_withdrawal_penalty_bps: Option<u16>,
+    pub start_time: i64,
+    pub unlock_time: i64,
+    pub bump: u8,
+}
+
+impl LockPosition {
+    /// Interest owed at maturity, fixed when the lock opens
+    pub fn promised_interest(&self) -> Result<u64> {
+        interest::calculate_interest(
+            self.amount,
+            self.interest_rate,
+            self.unlock_time.safe_sub(self.start_time)?,
+        )
+    }
+}
+
+/// Debt owed to `borrow_vault`, secured by shares escrowed from `collateral_vault`
+#[account]
+pub struct BorrowPosition {
+    pub owner: Pubkey,
+    pub borrow_vault: Pubkey,
+    pub collateral_vault: Pubkey,
+    pub collateral_shares: u64, // shares of the collateral vault held as collateral
+    pub collateral_basis: u64, // cost basis that moved with those shares
+    pub debt: u64, // principal plus interest as of `borrow_index`
+    pub principal: u64, // part of `debt` that was borrowed rather than accrued
+    pub borrow_index: u128, // borrow vault's index when `debt` was last updated
+    pub bump: u8,
+}
+
+impl BorrowPosition {
+    /// Roll `debt` forward to the borrow vault's current index, rounding up in its favour
+    pub fn accrue(&mut self, borrow_index: u128) -> Result<()> {
+        if self.debt > 0 && self.borrow_index > 0 {
+            let debt = (self.debt as u128)
+                .safe_mul(borrow_index)?
+                .div_ceil(self.borrow_index);
+            self.debt = to_u64(debt)?;
+        }
+        self.borrow_index = borrow_index;
+        Ok(())
+    }
+
+    /// Refuse if the debt exceeds `max_ltv_bps` of `collateral_value`
+    pub fn check_ltv(&self, collateral_value: u64, max_ltv_bps: u16) -> Result<()> {
+        require!(
+            self.debt <= bps_of(collateral_value, max_ltv_bps)?,
+            VaultError::ExceedsMaxLtv
+        );
+        Ok(())
+    }
+
+    /// `collateral_value * liquidation_threshold / debt`, WAD-scaled; below `WAD` the position
+    /// can be liquidated
+    pub fn health_factor(&self, collateral_value: u64, liquidation_threshold_bps: u16) -> Result<u128> {
+        if self.debt == 0 {
+            return Ok(u128::MAX);
+        }
+        (bps_of(collateral_value, liquidation_threshold_bps)? as u128)
+            .safe_mul(WAD)?
+            .safe_div(self.debt as u128)
+    }
+}
+
+/// Approval from `borrow_vault`'s authority to lend against shares of `collateral_vault`
+#[account]
+pub struct CollateralConfig {
+    pub borrow_vault: Pubkey,
+    pub collateral_vault: Pubkey,
+    pub price_feed: Option<Pubkey>, // values the collateral vault's token across mints
+    pub enabled: bool, // new borrows allowed; existing positions are unaffected
+    pub bump: u8,
+}
+
+/// Price of `token_mint` pushed by `authority`
+#[account]
+pub struct PriceFeed {
+    pub authority: Pubkey,
+    pub token_mint: Pubkey,
+    pub decimals: u8, // copied from the mint
+    pub price: u64, // quote units per whole token; feeds compared against each other must share a quote
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl PriceFeed {
+    /// The price, refused once it is older than `MAX_PRICE_AGE`
+    pub fn fresh_price(&self, current_time: i64) -> Result<u64> {
+        require!(
+            current_time.safe_sub(self.updated_at)? <= MAX_PRICE_AGE,
+            VaultError::StalePrice
+        );
+        Ok(self.price)
+    }
+}
+
+/// Withdrawal requested on a cooldown vault, claimable once `claimable_at` passes
+#[account]
+pub struct PendingWithdrawal {
+    pub owner: Pubkey,
+    pub vault: Pubkey,
+    pub principal_amount: u64, // paid from the vault token account
+    pub interest_amount: u64, // paid from the reward reserve
+    pub claimable_at: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct LockTerm {
+    pub duration: i64, // seconds; 0 means the slot is unused
+    pub interest_rate: u64, // in basis points
+    pub early_withdrawal_penalty_bps: Option<u16>, // None refuses early withdrawal
+}
+
+impl LockTerm {
+    pub const LEN: usize = 8 + 8 + (1 + 2);
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UserBalanceInfo {
+    pub deposited_amount: u64,
+    pub accrued_interest: u64,
+    pub total_balance: u64,
+    pub shares: u64,
+    pub last_update_time: i64,
+}
+
+#[event]
+pub struct DepositEvent {
+    pub user: Pubkey,
+    pub vault: Pubkey,
+    pub amount: u64, // gross amount sent by the user
+    pub net_amount: u64, // amount received by the vault after transfer fees
+    pub fee_amount: u64, // entry fee withheld from `net_amount`
+    pub shares: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawEvent {
+    pub user: Pubkey,
+    pub vault: Pubkey,
+    pub amount: u64, // gross amount debited from the position
+    pub net_amount: u64, // amount received by the user after transfer and exit fees
+    pub fee_amount: u64, // exit fee withheld from `amount`
+    pub shares: u64,
+    pub interest_amount: u64,
+    pub principal_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PositionClosedEvent {
+    pub user: Pubkey,
+    pub vault: Pubkey,
+    pub dust_shares: u64, // worthless shares burned into the vault on close
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawRequestedEvent {
+    pub user: Pubkey,
+    pub vault: Pubkey,
+    pub amount: u64, // gross amount debited from the position
+    pub fee_amount: u64,
+    pub shares: u64,
+    pub interest_amount: u64, // queued after fees
+    pub principal_amount: u64, // queued after fees
+    pub claimable_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawClaimedEvent {
+    pub user: Pubkey,
+    pub vault: Pubkey,
+    pub interest_amount: u64,
+    pub principal_amount: u64,
+    pub net_amount: u64, // amount received by the user after transfer fees
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalCooldownUpdatedEvent {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub old_withdrawal_cooldown: i64,
+    pub new_withdrawal_cooldown: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LockedDepositEvent {
+    pub user: Pubkey,
+    pub vault: Pubkey,
+    pub lock_id: u64,
+    pub term_id: u8,
+    pub amount: u64,
+    pub net_amount: u64,
+    pub interest_rate: u64,
+    pub unlock_time: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LockedWithdrawEvent {
+    pub user: Pubkey,
+    pub vault: Pubkey,
+    pub lock_id: u64,
+    pub principal_amount: u64,
+    pub interest_amount: u64,
+    pub penalty_amount: u64,
+    pub net_amount: u64,
+    pub early: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DepositCapsUpdatedEvent {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub max_total_deposits: Option<u64>,
+    pub max_per_user: Option<u64>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LockTermUpdatedEvent {
+    pub vault: Pubkey,
+    pub term_id: u8,
+    pub duration: i64,
+    pub interest_rate: u64,
+    pub early_withdrawal_penalty_bps: Option<u16>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultParamsUpdatedEvent {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub old_interest_rate: u64,
+    pub new_interest_rate: u64,
+    pub old_min_deposit: u64,
+    pub new_min_deposit: u64,
+    pub old_interest_model: InterestModel,
+    pub new_interest_model: InterestModel,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RateCurveUpdatedEvent {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub rate_curve: Option<RateCurve>,
+    pub interest_rate: u64, // rate now in force
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeesUpdatedEvent {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub old_fees: FeeConfig,
+    pub new_fees: FeeConfig,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeChargedEvent {
+    pub vault: Pubkey,
+    pub user: Option<Pubkey>, // None for fees charged on accrual
+    pub kind: FeeKind,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeesCollectedEvent {
+    pub vault: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
+    pub outstanding: u64, // reserve fees left owed because the reserve ran short
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-pub struct InitializeVault<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 1 + 8,
-        seeds = [b"vault", token_mint.key().as_ref()],
-        bump
-    )]
-    pub vault: Account<'info, Vault>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub token_mint: Account<'info, Mint>,
-    
-    #[account(
-        init,
-        payer = authority,
-        token::mint = token_mint,
-        token::authority = vault,
-        token::token_program = token_program,
-        seeds = [b"vault-token", token_mint.key().as_ref()],
-        bump
-    )]
-    pub token_vault: Account<'info, TokenAccount>,
-    
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
+#[event]
+pub struct SunsetStartedEvent {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub deadline: i64,
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-pub struct Deposit<'info> {
-    #[account(
-        mut,
-        seeds = [b"vault", vault.token_mint.as_ref()],
-        bump = vault.bump
-    )]
-    pub vault: Account<'info, Vault>,
-    
-    #[account(
-        init_if_needed,
-        payer = user,
-        space = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 8,
-        seeds = [b"user-position", vault.key().as_ref(), user.key().as_ref()],
-        bump
-    )]
-    pub user_position: Account<'info, UserPosition>,
-    
-    #[account(mut)]
-    pub user: Signer<'info>,
-    
-    #[account(
-        mut,
-        constraint = user_token_account.owner == user.key(),
-        constraint = user_token_account.mint == vault.token_mint
-    )]
-    pub user_token_account: Account<'info, TokenAccount>,
-    
-    #[account(
-        mut,
-        seeds = [b"vault-token", vault.token_mint.as_ref()],
-        bump
-    )]
-    pub vault_token_account: Account<'info, TokenAccount>,
-    
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
+#[event]
+pub struct VaultClosedEvent {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub swept_amount: u64, // unclaimed tokens sent to the authority
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-pub struct Withdraw<'info> {
-    #[account(
-        mut,
-        seeds = [b"vault", vault.token_mint.as_ref()],
-        bump = vault.bump
-    )]
-    pub vault: Account<'info, Vault>,
-    
-    #[account(
-        mut,
-        seeds = [b"user-position", vault.key().as_ref(), user.key().as_ref()],
-        bump
-    )]
-    pub user_position: Account<'info, UserPosition>,
-    
-    #[account(mut)]
-    pub user: Signer<'info>,
-    
-    #[account(
-        mut,
-        constraint = user_token_account.owner == user.key(),
-        constraint = user_token_account.mint == vault.token_mint
-    )]
-    pub user_token_account: Account<'info, TokenAccount>,
-    
-    #[account(
-        mut,
-        seeds = [b"vault-token", vault.token_mint.as_ref()],
-        bump
-    )]
-    pub vault_token_account: Account<'info, TokenAccount>,
-    
-    pub token_program: Program<'info, Token>,
+#[event]
+pub struct GuardianUpdatedEvent {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub old_guardian: Option<Pubkey>,
+    pub new_guardian: Option<Pubkey>,
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-pub struct GetUserBalance<'info> {
-    pub vault: Account<'info, Vault>,
-    pub user_position: Account<'info, UserPosition>,
+#[event]
+pub struct OutflowLimitUpdatedEvent {
+    pub vault: Pubkey,
+    pub updated_by: Pubkey, // authority or guardian
+    pub old_outflow_limit: OutflowLimit,
+    pub new_outflow_limit: OutflowLimit,
+    pub timestamp: i64,
 }
 
-#[account]
-pub struct Vault {
+#[event]
+pub struct BorrowEvent {
+    pub user: Pubkey,
+    pub borrow_vault: Pubkey,
+    pub collateral_vault: Pubkey,
+    pub amount: u64,
+    pub collateral_shares: u64, // added by this borrow
+    pub debt: u64, // total after this borrow
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RepayEvent {
+    pub user: Pubkey,
+    pub borrow_vault: Pubkey,
+    pub interest_amount: u64, // sent to the reward reserve
+    pub principal_amount: u64, // sent to the vault token account
+    pub debt: u64, // remaining
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CollateralReleasedEvent {
+    pub user: Pubkey,
+    pub borrow_vault: Pubkey,
+    pub collateral_vault: Pubkey,
+    pub shares: u64,
+    pub debt: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BorrowConfigUpdatedEvent {
+    pub vault: Pubkey,
     pub authority: Pubkey,
-    pub token_mint: Pubkey,
-    pub token_vault: Pubkey,
-    pub interest_rate: u64, // in basis points
-    pub min_deposit: u64,
-    pub total_deposited: u64,
-    pub bump: u8,
-    pub created_at: i64,
+    pub borrow_config: BorrowConfig,
+    pub timestamp: i64,
 }
 
-#[account]
-pub struct UserPosition {
-    pub owner: Pubkey,
+#[event]
+pub struct CollateralConfigUpdatedEvent {
+    pub borrow_vault: Pubkey,
+    pub collateral_vault: Pubkey,
+    pub price_feed: Option<Pubkey>,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardStreamAddedEvent {
     pub vault: Pubkey,
-    pub deposited_amount: u64,
-    pub accrued_interest: u64,
-    pub last_update_time: i64,
-    pub deposit_count: u64,
-    pub withdraw_count: u64,
+    pub reward_stream: Pubkey,
+    pub index: u8,
+    pub reward_mint: Pubkey,
+    pub emission_rate: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub timestamp: i64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize)]
-pub struct UserBalanceInfo {
-    pub deposited_amount: u64,
-    pub accrued_interest: u64,
-    pub total_balance: u64,
-    pub last_update_time: i64,
+#[event]
+pub struct RewardStreamSweptEvent {
+    pub vault: Pubkey,
+    pub reward_stream: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
 }
 
 #[event]
-pub struct DepositEvent {
+pub struct RewardStreamClosedEvent {
+    pub vault: Pubkey,
+    pub reward_stream: Pubkey,
+    pub swept_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardStreamExtendedEvent {
+    pub vault: Pubkey,
+    pub reward_stream: Pubkey,
+    pub old_end_time: i64,
+    pub new_end_time: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardsClaimedEvent {
     pub user: Pubkey,
     pub vault: Pubkey,
+    pub reward_stream: Pubkey,
     pub amount: u64,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct WithdrawEvent {
-    pub user: Pubkey,
+pub struct StrategyAddedEvent {
+    pub vault: Pubkey,
+    pub strategy: Pubkey,
+    pub strategy_program: Pubkey,
+    pub deposit_account: Pubkey,
+    pub allocation_cap: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AllocationCapUpdatedEvent {
+    pub strategy: Pubkey,
+    pub old_cap: u64,
+    pub new_cap: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AllocationEvent {
+    pub strategy: Pubkey,
+    pub amount: u64,
+    pub allocated: u64, // held by the strategy afterwards
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DeallocationEvent {
+    pub strategy: Pubkey,
+    pub amount: u64,
+    pub gain: u64, // returned on top of `amount`, credited to depositors
+    pub allocated: u64, // held by the strategy afterwards
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct HarvestEvent {
+    pub strategy: Pubkey,
+    pub amount: u64,
+    pub total_harvested: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LossThresholdUpdatedEvent {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub old_threshold_bps: Option<u16>,
+    pub new_threshold_bps: Option<u16>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StrategyReportEvent {
+    pub vault: Pubkey,
+    pub strategy: Pubkey,
+    pub gain: u64,
+    pub loss: u64,
+    pub allocated: u64, // held by the strategy afterwards
+    pub total_assets: u64, // of the share pool afterwards
+    pub withdraw_only: bool, // deposits are paused
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FlashBorrowEvent {
+    pub borrower: Pubkey,
+    pub vault: Pubkey,
+    pub amount: u64,
+    pub fee: u64, // due at `flash_repay`
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FlashRepayEvent {
+    pub payer: Pubkey,
+    pub vault: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+    pub treasury_fee: u64, // part of `fee` owed to the treasury; the rest is depositor interest
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidationEvent {
+    pub liquidator: Pubkey,
+    pub borrower: Pubkey,
+    pub borrow_vault: Pubkey,
+    pub collateral_vault: Pubkey,
+    pub interest_amount: u64, // repaid into the reward reserve
+    pub principal_amount: u64, // repaid into the vault token account
+    pub seized_shares: u64, // collateral shares moved to the liquidator
+    pub health_factor: u128, // WAD-scaled, before the liquidation
+    pub debt: u64, // remaining
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PriceFeedUpdatedEvent {
+    pub vault: Pubkey,
+    pub price_feed: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PriceUpdatedEvent {
+    pub price_feed: Pubkey,
+    pub price: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PauseUpdatedEvent {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub paused_flags: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityProposedEvent {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityAcceptedEvent {
+    pub vault: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityTransferCancelledEvent {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub cancelled_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReserveFundedEvent {
     pub vault: Pubkey,
+    pub funder: Pubkey,
     pub amount: u64,
+    pub reserve_balance: u64,
     pub timestamp: i64,
 }
 
@@ -352,4 +5057,120 @@ pub enum VaultError {
     InsufficientDepositAmount,
     #[msg("Insufficient balance for withdrawal")]
     InsufficientBalance,
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+    #[msg("Reward reserve cannot cover the accrued interest")]
+    InsufficientReserve,
+    #[msg("Deposit is too small to mint any shares")]
+    ZeroShares,
+    #[msg("Signer is not the vault authority")]
+    Unauthorized,
+    #[msg("New authority must differ from the current authority")]
+    InvalidAuthority,
+    #[msg("Signer is not the pending vault authority")]
+    NotPendingAuthority,
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+    #[msg("This vault operation is paused")]
+    Paused,
+    #[msg("Unknown pause flags")]
+    InvalidPauseFlags,
+    #[msg("Math overflow")]
+    MathOverflow,
+    #[msg("Token program does not match the vault's token program")]
+    InvalidTokenProgram,
+    #[msg("Compounding period must be greater than zero")]
+    InvalidInterestModel,
+    #[msg("Lock term is not configured or its parameters are invalid")]
+    InvalidLockTerm,
+    #[msg("Locked deposit has not reached its unlock time")]
+    LockNotMatured,
+    #[msg("Treasury account does not match the vault")]
+    InvalidTreasury,
+    #[msg("Fee cannot exceed 10000 basis points")]
+    InvalidFee,
+    #[msg("Position still holds shares or open locks")]
+    PositionNotEmpty,
+    #[msg("Position does not belong to this user and vault")]
+    InvalidPosition,
+    #[msg("Vault is being sunset")]
+    VaultSunsetting,
+    #[msg("Vault has not begun its sunset")]
+    VaultNotSunsetting,
+    #[msg("Sunset deadline must not be before any open lock matures")]
+    InvalidSunsetDeadline,
+    #[msg("Depositors remain and the sunset deadline has not passed")]
+    SunsetInProgress,
+    #[msg("Deposit would exceed the vault's total deposit cap")]
+    DepositCapExceeded,
+    #[msg("Deposit would exceed the per-user deposit cap")]
+    UserDepositCapExceeded,
+    #[msg("Withdrawals on this vault go through request_withdraw")]
+    WithdrawalCooldownActive,
+    #[msg("Vault has no withdrawal cooldown; use withdraw")]
+    WithdrawalCooldownDisabled,
+    #[msg("Withdrawal cooldown cannot be negative")]
+    InvalidWithdrawalCooldown,
+    #[msg("Pending withdrawal is still in its cooldown")]
+    WithdrawalNotClaimable,
+    #[msg("Withdrawal would exceed the vault's outflow limit for this window")]
+    OutflowLimitExceeded,
+    #[msg("Outflow limit needs a positive window and at most 10000 basis points")]
+    InvalidOutflowLimit,
+    #[msg("Guardian may only tighten the outflow limit")]
+    GuardianCannotLoosen,
+    #[msg("Optimal utilization must be between 1 and 10000 basis points")]
+    InvalidRateCurve,
+    #[msg("Borrowing is not enabled on this vault")]
+    BorrowingDisabled,
+    #[msg("Collateral vault must be a different vault from the one borrowed from")]
+    InvalidCollateralVault,
+    #[msg("Not enough idle liquidity in the vault")]
+    InsufficientLiquidity,
+    #[msg("Debt would exceed the maximum loan-to-value ratio")]
+    ExceedsMaxLtv,
+    #[msg("Max LTV must not exceed the liquidation threshold, which must leave room for the bonus")]
+    InvalidBorrowConfig,
+    #[msg("Position is healthy and cannot be liquidated")]
+    PositionHealthy,
+    #[msg("Price feed does not match the vault")]
+    InvalidPriceFeed,
+    #[msg("Both vaults need a price feed to value collateral in another mint")]
+    MissingPriceFeed,
+    #[msg("Price feed has not been updated recently enough")]
+    StalePrice,
+    #[msg("Reward stream is invalid or does not match the vault")]
+    InvalidRewardStream,
+    #[msg("No rewards to claim")]
+    NoRewardsToClaim,
+    #[msg("A flash loan is outstanding on this vault")]
+    FlashLoanActive,
+    #[msg("No flash loan to repay")]
+    NoFlashLoan,
+    #[msg("Flash loan must be repaid in full by a later flash_repay in the same transaction")]
+    InvalidFlashLoan,
+    #[msg("Allocation would exceed the strategy's cap")]
+    AllocationCapExceeded,
+    #[msg("Strategy moved a different amount than requested")]
+    StrategyMismatch,
+    #[msg("Loss threshold cannot exceed 10000 bps")]
+    InvalidLossThreshold,
+    #[msg("Vault cannot be closed while loans against it are outstanding")]
+    OutstandingBorrows,
+    #[msg("Reward stream has not ended yet")]
+    RewardStreamActive,
+    #[msg("Vault cannot be closed while funds are deployed to strategies")]
+    StrategyFundsOutstanding,
+    #[msg("Strategy deposit account must hold the vault's mint and not belong to the vault")]
+    InvalidDepositAccount,
+    #[msg("Vault has outstanding shares but no assets behind them")]
+    VaultInsolvent,
+    #[msg("Borrow vault does not accept this collateral vault")]
+    CollateralNotAccepted,
+    #[msg("Reward stream still owes shareholders rewards they have not claimed")]
+    RewardsUnclaimed,
+    #[msg("Vault cannot be closed while reward streams are open")]
+    RewardStreamsOpen,
+    #[msg("Vault cannot be closed while its shares secure loans")]
+    OutstandingCollateral,
 }
\ No newline at end of file
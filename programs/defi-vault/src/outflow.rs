@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::fees::bps_of;
+use crate::interest::BPS_DENOMINATOR;
+use crate::VaultError;
+
+/// Cap on how much may leave a vault within one window; with neither cap set it is off
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct OutflowLimit {
+    pub window: i64, // seconds
+    pub max_amount: Option<u64>, // tokens per window
+    pub max_tvl_bps: Option<u16>, // share of TVL at the start of the window
+}
+
+impl OutflowLimit {
+    pub const LEN: usize = 8 + (1 + 8) + (1 + 2);
+
+    pub fn validate(&self) -> Result<()> {
+        if self.is_enabled() {
+            require!(self.window > 0, VaultError::InvalidOutflowLimit);
+        }
+        if let Some(bps) = self.max_tvl_bps {
+            require!(bps as u128 <= BPS_DENOMINATOR, VaultError::InvalidOutflowLimit);
+        }
+        Ok(())
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.max_amount.is_some() || self.max_tvl_bps.is_some()
+    }
+
+    /// Most that may leave in one window given `tvl` at its start, or `None` if unlimited
+    pub fn allowance(&self, tvl: u64) -> Result<Option<u64>> {
+        let by_tvl = self.max_tvl_bps.map(|bps| bps_of(tvl, bps)).transpose()?;
+        Ok(match (self.max_amount, by_tvl) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        })
+    }
+
+    /// Whether `self` never allows more outflow than `current`: no cap is loosened or
+    /// removed and the window is no shorter
+    pub fn is_tighter_than(&self, current: &OutflowLimit) -> bool {
+        fn tighter<T: PartialOrd>(new: Option<T>, current: Option<T>) -> bool {
+            match (new, current) {
+                (_, None) => true,
+                (None, Some(_)) => false,
+                (Some(new), Some(current)) => new <= current,
+            }
+        }
+
+        (!current.is_enabled() || self.window >= current.window)
+            && tighter(self.max_amount, current.max_amount)
+            && tighter(self.max_tvl_bps, current.max_tvl_bps)
+    }
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::VaultError;
+
+/// Checked arithmetic that surfaces overflow as `VaultError::MathOverflow`
+/// instead of panicking or wrapping. All balance updates go through here.
+pub trait SafeMath: Sized {
+    fn safe_add(self, rhs: Self) -> Result<Self>;
+    fn safe_sub(self, rhs: Self) -> Result<Self>;
+    fn safe_mul(self, rhs: Self) -> Result<Self>;
+    fn safe_div(self, rhs: Self) -> Result<Self>;
+}
+
+macro_rules! impl_safe_math {
+    ($($t:ty),*) => {
+        $(
+            impl SafeMath for $t {
+                fn safe_add(self, rhs: Self) -> Result<Self> {
+                    self.checked_add(rhs).ok_or_else(|| error!(VaultError::MathOverflow))
+                }
+
+                fn safe_sub(self, rhs: Self) -> Result<Self> {
+                    self.checked_sub(rhs).ok_or_else(|| error!(VaultError::MathOverflow))
+                }
+
+                fn safe_mul(self, rhs: Self) -> Result<Self> {
+                    self.checked_mul(rhs).ok_or_else(|| error!(VaultError::MathOverflow))
+                }
+
+                fn safe_div(self, rhs: Self) -> Result<Self> {
+                    self.checked_div(rhs).ok_or_else(|| error!(VaultError::MathOverflow))
+                }
+            }
+        )*
+    };
+}
+
+impl_safe_math!(u64, u128, i64);
+
+/// Checked `u128 -> u64` narrowing
+pub fn to_u64(value: u128) -> Result<u64> {
+    u64::try_from(value).map_err(|_| error!(VaultError::MathOverflow))
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+
+/// Strategy instruction told that `amount` has just been transferred to its deposit account
+pub const DEPOSIT_FUNDS: &str = "deposit_funds";
+/// Strategy instruction that returns `amount` to the vault token account
+pub const WITHDRAW_FUNDS: &str = "withdraw_funds";
+/// Strategy instruction that sends realized yield to the reward reserve
+pub const HARVEST: &str = "harvest";
+
+/// Anchor-style discriminator for a strategy instruction, so strategies can be
+/// ordinary Anchor programs with `deposit_funds`, `withdraw_funds` and `harvest`
+pub fn discriminator(name: &str) -> [u8; 8] {
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash(format!("global:{name}").as_bytes()).to_bytes()[..8]);
+    discriminator
+}
+
+/// Call a strategy instruction with the vault's `Strategy` account signing, which is how
+/// the strategy knows which vault is calling. The vault itself never signs.
+///
+/// Every strategy instruction takes the same leading accounts, followed by whatever
+/// the strategy itself needs, forwarded from the remaining accounts:
+/// 0. `[signer]` the vault's `Strategy` account
+/// 1. `[]` token mint
+/// 2. `[writable]` vault token account
+/// 3. `[writable]` reward reserve
+/// 4. `[]` token program
+pub fn invoke<'info>(
+    strategy_program: &AccountInfo<'info>,
+    name: &str,
+    amount: Option<u64>,
+    leading: [&AccountInfo<'info>; 5],
+    remaining: &[AccountInfo<'info>],
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let mut data = discriminator(name).to_vec();
+    if let Some(amount) = amount {
+        data.extend_from_slice(&amount.to_le_bytes());
+    }
+
+    let [strategy, token_mint, vault_token_account, reward_reserve, token_program] = leading;
+    let mut accounts = vec![
+        AccountMeta::new_readonly(strategy.key(), true),
+        AccountMeta::new_readonly(token_mint.key(), false),
+        AccountMeta::new(vault_token_account.key(), false),
+        AccountMeta::new(reward_reserve.key(), false),
+        AccountMeta::new_readonly(token_program.key(), false),
+    ];
+    accounts.extend(remaining.iter().map(|account| AccountMeta {
+        pubkey: account.key(),
+        is_signer: account.is_signer,
+        is_writable: account.is_writable,
+    }));
+
+    let mut infos: Vec<AccountInfo<'info>> = leading.iter().map(|&info| info.clone()).collect();
+    infos.extend(remaining.iter().cloned());
+    infos.push(strategy_program.clone());
+
+    let instruction = Instruction {
+        program_id: strategy_program.key(),
+        accounts,
+        data,
+    };
+    invoke_signed(&instruction, &infos, signer_seeds)?;
+    Ok(())
+}
@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+declare_id!("7k18NnvEkFtzUCibSy5EKhZKpxaYFk86Qct1AEAH5bK6");
+
+/// Strategy for local testing of the vault's strategy adapter.
+///
+/// It parks whatever the vault deposits in a `funds` token account and treats anything
+/// above that as yield, so tests simulate returns by minting straight into `funds`.
+/// Only the vault's `Strategy` account, which signs every call, may drive it.
+#[program]
+pub mod mock_strategy {
+    use super::*;
+
+    /// Set up the state and funds accounts for one vault's `Strategy` account
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+        state.strategy = ctx.accounts.strategy.key();
+        state.deposited = 0;
+        state.bump = ctx.bumps.state;
+        Ok(())
+    }
+
+    /// Record `amount` the vault has just transferred into `funds`
+    pub fn deposit_funds(ctx: Context<StrategyAccounts>, amount: u64) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+        state.deposited = state.deposited.checked_add(amount).ok_or(StrategyError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Return `amount` of principal to the vault token account
+    pub fn withdraw_funds(ctx: Context<StrategyAccounts>, amount: u64) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+        state.deposited = state.deposited.checked_sub(amount).ok_or(StrategyError::MathOverflow)?;
+
+        let to = ctx.accounts.vault_token_account.to_account_info();
+        ctx.accounts.pay_out(to, amount)
+    }
+
+    /// Send everything above the deposited principal to the reward reserve
+    pub fn harvest(ctx: Context<StrategyAccounts>) -> Result<()> {
+        let gain = ctx.accounts.funds.amount.saturating_sub(ctx.accounts.state.deposited);
+        if gain == 0 {
+            return Ok(());
+        }
+
+        let to = ctx.accounts.reward_reserve.to_account_info();
+        ctx.accounts.pay_out(to, gain)
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    /// CHECK: the vault's `Strategy` account for this program; only its key is stored
+    pub strategy: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 32 + 8 + 1,
+        seeds = [b"state", strategy.key().as_ref()],
+        bump
+    )]
+    pub state: Account<'info, StrategyState>,
+
+    #[account(mint::token_program = token_program)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"funds", state.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = state,
+        token::token_program = token_program
+    )]
+    pub funds: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// The vault's fixed leading accounts, then this strategy's own
+#[derive(Accounts)]
+pub struct StrategyAccounts<'info> {
+    pub strategy: Signer<'info>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub reward_reserve: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    #[account(
+        mut,
+        seeds = [b"state", strategy.key().as_ref()],
+        bump = state.bump,
+        has_one = strategy
+    )]
+    pub state: Account<'info, StrategyState>,
+
+    #[account(
+        mut,
+        seeds = [b"funds", state.key().as_ref()],
+        bump
+    )]
+    pub funds: InterfaceAccount<'info, TokenAccount>,
+}
+
+impl<'info> StrategyAccounts<'info> {
+    fn pay_out(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        let strategy = self.strategy.key();
+        let seeds = &[b"state", strategy.as_ref(), &[self.state.bump]];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = TransferChecked {
+            from: self.funds.to_account_info(),
+            mint: self.token_mint.to_account_info(),
+            to,
+            authority: self.state.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, signer);
+        token_interface::transfer_checked(cpi_ctx, amount, self.token_mint.decimals)
+    }
+}
+
+#[account]
+pub struct StrategyState {
+    pub strategy: Pubkey, // the vault's `Strategy` account, the only caller
+    pub deposited: u64, // principal the vault has placed here
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum StrategyError {
+    #[msg("Math overflow")]
+    MathOverflow,
+}
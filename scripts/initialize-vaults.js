@@ -1,6 +1,6 @@
 const { Connection, PublicKey, Keypair, SystemProgram, SYSVAR_RENT_PUBKEY } = require('@solana/web3.js');
 const { Program, AnchorProvider, Wallet, BN } = require('@coral-xyz/anchor');
-const { TOKEN_PROGRAM_ID } = require('@solana/spl-token');
+const { TOKEN_PROGRAM_ID, getOrCreateAssociatedTokenAccount } = require('@solana/spl-token');
 const fs = require('fs');
 const path = require('path');
 
@@ -92,6 +92,11 @@ async function initializeVaults() {
         PROGRAM_ID
       );
       
+      const [rewardReservePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from('vault-reserve'), tokenMint.toBuffer()],
+        PROGRAM_ID
+      );
+      
       // Check if vault already exists
       try {
         await program.account.vault.fetch(vaultPda);
@@ -103,13 +108,26 @@ async function initializeVaults() {
       }
       
       try {
+        const treasury = await getOrCreateAssociatedTokenAccount(
+          connection,
+          walletKeypair,
+          tokenMint,
+          wallet.publicKey,
+          false,
+          'confirmed',
+          undefined,
+          TOKEN_PROGRAM_ID
+        );
+        
         const tx = await program.methods
-          .initializeVault(new BN(token.interestRate), new BN(token.minDeposit))
+          .initializeVault(new BN(0), new BN(token.interestRate), new BN(token.minDeposit), { simple: {} })
           .accounts({
             vault: vaultPda,
             authority: wallet.publicKey,
             tokenMint: tokenMint,
             tokenVault: tokenVaultPda,
+            rewardReserve: rewardReservePda,
+            treasury: treasury.address,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
             rent: SYSVAR_RENT_PUBKEY,
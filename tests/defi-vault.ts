@@ -1,11 +1,17 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { DefiVault } from "../target/types/defi_vault";
-import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
+import { MockStrategy } from "../target/types/mock_strategy";
+import { PublicKey, Keypair, SystemProgram, Transaction, sendAndConfirmTransaction } from "@solana/web3.js";
 import { 
   TOKEN_PROGRAM_ID, 
+  TOKEN_2022_PROGRAM_ID,
+  ExtensionType,
   createMint, 
   createAccount, 
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  getMintLen,
   mintTo,
   getAccount
 } from "@solana/spl-token";
@@ -22,6 +28,9 @@ describe("defi-vault", () => {
   let userTokenAccount: PublicKey;
   let vaultPda: PublicKey;
   let vaultTokenPda: PublicKey;
+  let rewardReservePda: PublicKey;
+  let authorityTokenAccount: PublicKey;
+  let treasuryTokenAccount: PublicKey;
   let userPositionPda: PublicKey;
   
   const user = Keypair.generate();
@@ -57,6 +66,40 @@ describe("defi-vault", () => {
       TOKEN_PROGRAM_ID
     );
 
+    // Create and fund the authority's token account for reserve top-ups
+    authorityTokenAccount = await createAccount(
+      provider.connection,
+      authority.payer,
+      mint,
+      authority.publicKey,
+      undefined,
+      undefined,
+      TOKEN_PROGRAM_ID
+    );
+
+    await mintTo(
+      provider.connection,
+      authority.payer,
+      mint,
+      authorityTokenAccount,
+      authority.payer,
+      10 * 1000000, // 10 tokens
+      undefined,
+      undefined,
+      TOKEN_PROGRAM_ID
+    );
+
+    // Separate authority-owned account that collects penalties and fees
+    treasuryTokenAccount = await createAccount(
+      provider.connection,
+      authority.payer,
+      mint,
+      authority.publicKey,
+      Keypair.generate(),
+      undefined,
+      TOKEN_PROGRAM_ID
+    );
+
     // Mint tokens to user
     await mintTo(
       provider.connection,
@@ -81,6 +124,11 @@ describe("defi-vault", () => {
       program.programId
     );
 
+    [rewardReservePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault-reserve"), mint.toBuffer()],
+      program.programId
+    );
+
     [userPositionPda] = PublicKey.findProgramAddressSync(
       [Buffer.from("user-position"), vaultPda.toBuffer(), user.publicKey.toBuffer()],
       program.programId
@@ -89,12 +137,14 @@ describe("defi-vault", () => {
 
   it("Initializes the vault", async () => {
     await program.methods
-      .initializeVault(new anchor.BN(interestRate), new anchor.BN(minDeposit))
+      .initializeVault(new anchor.BN(0), new anchor.BN(interestRate), new anchor.BN(minDeposit), { simple: {} })
       .accounts({
         vault: vaultPda,
         authority: authority.publicKey,
         tokenMint: mint,
         tokenVault: vaultTokenPda,
+        rewardReserve: rewardReservePda,
+        treasury: treasuryTokenAccount,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -106,6 +156,27 @@ describe("defi-vault", () => {
     assert.equal(vault.tokenMint.toString(), mint.toString());
     assert.equal(vault.interestRate.toNumber(), interestRate);
     assert.equal(vault.minDeposit.toNumber(), minDeposit);
+    assert.equal(vault.rewardReserve.toString(), rewardReservePda.toString());
+    assert.equal(vault.treasury.toString(), treasuryTokenAccount.toString());
+  });
+
+  it("Funds the reward reserve", async () => {
+    const fundAmount = 1 * 1000000; // 1 token
+
+    await program.methods
+      .fundReserve(new anchor.BN(fundAmount))
+      .accounts({
+        vault: vaultPda,
+        authority: authority.publicKey,
+        tokenMint: mint,
+        authorityTokenAccount: authorityTokenAccount,
+        rewardReserve: rewardReservePda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const reserve = await getAccount(provider.connection, rewardReservePda, undefined, TOKEN_PROGRAM_ID);
+    assert.equal(Number(reserve.amount), fundAmount);
   });
 
   it("Deposits tokens into the vault", async () => {
@@ -117,6 +188,7 @@ describe("defi-vault", () => {
         vault: vaultPda,
         userPosition: userPositionPda,
         user: user.publicKey,
+        tokenMint: mint,
         userTokenAccount: userTokenAccount,
         vaultTokenAccount: vaultTokenPda,
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -129,9 +201,12 @@ describe("defi-vault", () => {
     const userPosition = await program.account.userPosition.fetch(userPositionPda);
     assert.equal(userPosition.depositedAmount.toNumber(), depositAmount);
     assert.equal(userPosition.owner.toString(), user.publicKey.toString());
+    // First deposit mints shares 1:1
+    assert.equal(userPosition.shares.toNumber(), depositAmount);
 
     const vault = await program.account.vault.fetch(vaultPda);
     assert.equal(vault.totalDeposited.toNumber(), depositAmount);
+    assert.equal(vault.totalShares.toNumber(), depositAmount);
   });
 
   it("Calculates interest correctly", async () => {
@@ -147,6 +222,7 @@ describe("defi-vault", () => {
         vault: vaultPda,
         userPosition: userPositionPda,
         user: user.publicKey,
+        tokenMint: mint,
         userTokenAccount: userTokenAccount,
         vaultTokenAccount: vaultTokenPda,
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -156,9 +232,36 @@ describe("defi-vault", () => {
       .signers([user])
       .rpc();
 
-    const userPosition = await program.account.userPosition.fetch(userPositionPda);
     // Interest should be accrued (though minimal due to short time)
-    assert.isTrue(userPosition.accruedInterest.toNumber() >= 0);
+    const vault = await program.account.vault.fetch(vaultPda);
+    assert.isTrue(vault.totalAccruedInterest.toNumber() >= 0);
+
+    const balance = await program.methods
+      .getUserBalance()
+      .accounts({ vault: vaultPda, userPosition: userPositionPda })
+      .view();
+    assert.isTrue(balance.totalBalance.toNumber() >= balance.depositedAmount.toNumber());
+  });
+
+  it("Previews share conversions", async () => {
+    const amount = new anchor.BN(1000000);
+
+    const sharesIn = await program.methods
+      .previewDeposit(amount)
+      .accounts({ vault: vaultPda })
+      .view();
+    const sharesOut = await program.methods
+      .previewWithdraw(amount)
+      .accounts({ vault: vaultPda })
+      .view();
+    const assets = await program.methods
+      .convertToAssets(sharesOut)
+      .accounts({ vault: vaultPda })
+      .view();
+
+    // Rounding always favours the vault
+    assert.isTrue(sharesIn.lte(sharesOut));
+    assert.isTrue(assets.gte(amount));
   });
 
   it("Withdraws tokens from the vault", async () => {
@@ -172,8 +275,10 @@ describe("defi-vault", () => {
         vault: vaultPda,
         userPosition: userPositionPda,
         user: user.publicKey,
+        tokenMint: mint,
         userTokenAccount: userTokenAccount,
         vaultTokenAccount: vaultTokenPda,
+        rewardReserve: rewardReservePda,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .signers([user])
@@ -185,11 +290,19 @@ describe("defi-vault", () => {
     // Check that tokens were withdrawn
     assert.isTrue(Number(finalBalance.amount) > Number(initialBalance.amount));
     assert.equal(userPosition.withdrawCount.toNumber(), 1);
+
+    // Interest is paid from the reserve, so the vault only releases principal
+    const vault = await program.account.vault.fetch(vaultPda);
+    const vaultTokens = await getAccount(provider.connection, vaultTokenPda, undefined, TOKEN_PROGRAM_ID);
+    assert.equal(Number(vaultTokens.amount), vault.totalDeposited.toNumber());
   });
 
   it("Prevents withdrawal of more than available balance", async () => {
-    const userPosition = await program.account.userPosition.fetch(userPositionPda);
-    const excessiveAmount = userPosition.depositedAmount.toNumber() + userPosition.accruedInterest.toNumber() + 1000000;
+    const balance = await program.methods
+      .getUserBalance()
+      .accounts({ vault: vaultPda, userPosition: userPositionPda })
+      .view();
+    const excessiveAmount = balance.totalBalance.toNumber() + 1000000;
 
     try {
       await program.methods
@@ -198,8 +311,10 @@ describe("defi-vault", () => {
           vault: vaultPda,
           userPosition: userPositionPda,
           user: user.publicKey,
+          tokenMint: mint,
           userTokenAccount: userTokenAccount,
           vaultTokenAccount: vaultTokenPda,
+          rewardReserve: rewardReservePda,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
         .signers([user])
@@ -211,6 +326,2464 @@ describe("defi-vault", () => {
     }
   });
 
+  it("Updates vault parameters", async () => {
+    const newInterestRate = 750; // 7.5% APY
+
+    await program.methods
+      .updateVaultParams(new anchor.BN(newInterestRate), new anchor.BN(minDeposit), { compound: { period: new anchor.BN(86400) } })
+      .accounts({
+        vault: vaultPda,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+    const vault = await program.account.vault.fetch(vaultPda);
+    assert.equal(vault.interestRate.toNumber(), newInterestRate);
+    assert.equal(vault.minDeposit.toNumber(), minDeposit);
+    assert.equal(vault.interestModel.compound.period.toNumber(), 86400);
+  });
+
+  it("Rejects a zero compounding period", async () => {
+    try {
+      await program.methods
+        .updateVaultParams(new anchor.BN(interestRate), new anchor.BN(minDeposit), { compound: { period: new anchor.BN(0) } })
+        .accounts({
+          vault: vaultPda,
+          authority: authority.publicKey,
+        })
+        .rpc();
+
+      assert.fail("Should have failed with invalid interest model");
+    } catch (error) {
+      assert.include((error as Error).toString(), "InvalidInterestModel");
+    }
+  });
+
+  it("Prevents non-authority from updating vault parameters", async () => {
+    try {
+      await program.methods
+        .updateVaultParams(new anchor.BN(10000), new anchor.BN(0), { simple: {} })
+        .accounts({
+          vault: vaultPda,
+          authority: user.publicKey,
+        })
+        .signers([user])
+        .rpc();
+
+      assert.fail("Should have failed with unauthorized");
+    } catch (error) {
+      assert.include((error as Error).toString(), "Unauthorized");
+    }
+  });
+
+  it("Transfers vault authority in two steps", async () => {
+    const newAuthority = Keypair.generate();
+
+    await program.methods
+      .proposeAuthority(newAuthority.publicKey)
+      .accounts({ vault: vaultPda, authority: authority.publicKey })
+      .rpc();
+
+    let vault = await program.account.vault.fetch(vaultPda);
+    assert.equal(vault.pendingAuthority.toString(), newAuthority.publicKey.toString());
+
+    // Only the proposed key can accept
+    try {
+      await program.methods
+        .acceptAuthority()
+        .accounts({ vault: vaultPda, pendingAuthority: user.publicKey })
+        .signers([user])
+        .rpc();
+      assert.fail("Should have failed with not pending authority");
+    } catch (error) {
+      assert.include((error as Error).toString(), "NotPendingAuthority");
+    }
+
+    await program.methods
+      .acceptAuthority()
+      .accounts({ vault: vaultPda, pendingAuthority: newAuthority.publicKey })
+      .signers([newAuthority])
+      .rpc();
+
+    vault = await program.account.vault.fetch(vaultPda);
+    assert.equal(vault.authority.toString(), newAuthority.publicKey.toString());
+    assert.isNull(vault.pendingAuthority);
+
+    // Hand the vault back so later tests keep using the provider wallet
+    await program.methods
+      .proposeAuthority(authority.publicKey)
+      .accounts({ vault: vaultPda, authority: newAuthority.publicKey })
+      .signers([newAuthority])
+      .rpc();
+    await program.methods
+      .acceptAuthority()
+      .accounts({ vault: vaultPda, pendingAuthority: authority.publicKey })
+      .rpc();
+  });
+
+  it("Cancels a pending authority transfer", async () => {
+    await program.methods
+      .proposeAuthority(user.publicKey)
+      .accounts({ vault: vaultPda, authority: authority.publicKey })
+      .rpc();
+
+    await program.methods
+      .cancelAuthorityTransfer()
+      .accounts({ vault: vaultPda, authority: authority.publicKey })
+      .rpc();
+
+    const vault = await program.account.vault.fetch(vaultPda);
+    assert.isNull(vault.pendingAuthority);
+    assert.equal(vault.authority.toString(), authority.publicKey.toString());
+  });
+
+  it("Pauses deposits while still allowing withdrawals", async () => {
+    const PAUSE_DEPOSITS = 1;
+
+    await program.methods
+      .setPause(PAUSE_DEPOSITS)
+      .accounts({ vault: vaultPda, authority: authority.publicKey })
+      .rpc();
+
+    try {
+      await program.methods
+        .deposit(new anchor.BN(minDeposit))
+        .accounts({
+          vault: vaultPda,
+          userPosition: userPositionPda,
+          user: user.publicKey,
+          tokenMint: mint,
+          userTokenAccount: userTokenAccount,
+          vaultTokenAccount: vaultTokenPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([user])
+        .rpc();
+      assert.fail("Should have failed with paused");
+    } catch (error) {
+      assert.include((error as Error).toString(), "Paused");
+    }
+
+    await program.methods
+      .withdraw(new anchor.BN(minDeposit))
+      .accounts({
+        vault: vaultPda,
+        userPosition: userPositionPda,
+        user: user.publicKey,
+        tokenMint: mint,
+        userTokenAccount: userTokenAccount,
+        vaultTokenAccount: vaultTokenPda,
+        rewardReserve: rewardReservePda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    await program.methods
+      .setPause(0)
+      .accounts({ vault: vaultPda, authority: authority.publicKey })
+      .rpc();
+
+    const vault = await program.account.vault.fetch(vaultPda);
+    assert.equal(vault.pausedFlags, 0);
+  });
+
+  it("Supports Token-2022 mints", async () => {
+    const mint2022 = await createMint(
+      provider.connection,
+      authority.payer,
+      authority.publicKey,
+      authority.publicKey,
+      6,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const userTokenAccount2022 = await createAccount(
+      provider.connection,
+      authority.payer,
+      mint2022,
+      user.publicKey,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    await mintTo(
+      provider.connection,
+      authority.payer,
+      mint2022,
+      userTokenAccount2022,
+      authority.payer,
+      10 * 1000000,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    const treasury2022 = await createAccount(
+      provider.connection,
+      authority.payer,
+      mint2022,
+      authority.publicKey,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    const [vault2022Pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), mint2022.toBuffer()],
+      program.programId
+    );
+    const [vaultToken2022Pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault-token"), mint2022.toBuffer()],
+      program.programId
+    );
+    const [rewardReserve2022Pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault-reserve"), mint2022.toBuffer()],
+      program.programId
+    );
+    const [userPosition2022Pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user-position"), vault2022Pda.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initializeVault(new anchor.BN(0), new anchor.BN(interestRate), new anchor.BN(minDeposit), { simple: {} })
+      .accounts({
+        vault: vault2022Pda,
+        authority: authority.publicKey,
+        tokenMint: mint2022,
+        tokenVault: vaultToken2022Pda,
+        rewardReserve: rewardReserve2022Pda,
+        treasury: treasury2022,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    const vault = await program.account.vault.fetch(vault2022Pda);
+    assert.equal(vault.tokenProgram.toString(), TOKEN_2022_PROGRAM_ID.toString());
+
+    const depositAmount = 2 * 1000000;
+    await program.methods
+      .deposit(new anchor.BN(depositAmount))
+      .accounts({
+        vault: vault2022Pda,
+        userPosition: userPosition2022Pda,
+        user: user.publicKey,
+        tokenMint: mint2022,
+        userTokenAccount: userTokenAccount2022,
+        vaultTokenAccount: vaultToken2022Pda,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([user])
+      .rpc();
+
+    const vaultTokens = await getAccount(provider.connection, vaultToken2022Pda, undefined, TOKEN_2022_PROGRAM_ID);
+    assert.equal(Number(vaultTokens.amount), depositAmount);
+
+    // The vault is pinned to the program it was created with
+    try {
+      await program.methods
+        .deposit(new anchor.BN(depositAmount))
+        .accounts({
+          vault: vault2022Pda,
+          userPosition: userPosition2022Pda,
+          user: user.publicKey,
+          tokenMint: mint2022,
+          userTokenAccount: userTokenAccount2022,
+          vaultTokenAccount: vaultToken2022Pda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([user])
+        .rpc();
+      assert.fail("Should have failed with invalid token program");
+    } catch (error) {
+      assert.include((error as Error).toString(), "InvalidTokenProgram");
+    }
+  });
+
+  it("Credits only the net amount for transfer-fee mints", async () => {
+    const feeBps = 100; // 1% transfer fee
+    const feeMint = Keypair.generate();
+    const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+    const lamports = await provider.connection.getMinimumBalanceForRentExemption(mintLen);
+
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(
+        SystemProgram.createAccount({
+          fromPubkey: authority.publicKey,
+          newAccountPubkey: feeMint.publicKey,
+          space: mintLen,
+          lamports,
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeTransferFeeConfigInstruction(
+          feeMint.publicKey,
+          authority.publicKey,
+          authority.publicKey,
+          feeBps,
+          BigInt(1000000000),
+          TOKEN_2022_PROGRAM_ID
+        ),
+        createInitializeMintInstruction(feeMint.publicKey, 6, authority.publicKey, null, TOKEN_2022_PROGRAM_ID)
+      ),
+      [authority.payer, feeMint]
+    );
+
+    const feeUserTokenAccount = await createAccount(
+      provider.connection,
+      authority.payer,
+      feeMint.publicKey,
+      user.publicKey,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    await mintTo(
+      provider.connection,
+      authority.payer,
+      feeMint.publicKey,
+      feeUserTokenAccount,
+      authority.payer,
+      10 * 1000000,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    const feeTreasury = await createAccount(
+      provider.connection,
+      authority.payer,
+      feeMint.publicKey,
+      authority.publicKey,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    const [feeVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), feeMint.publicKey.toBuffer()],
+      program.programId
+    );
+    const [feeVaultTokenPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault-token"), feeMint.publicKey.toBuffer()],
+      program.programId
+    );
+    const [feeRewardReservePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault-reserve"), feeMint.publicKey.toBuffer()],
+      program.programId
+    );
+    const [feeUserPositionPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user-position"), feeVaultPda.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initializeVault(new anchor.BN(0), new anchor.BN(interestRate), new anchor.BN(minDeposit), { simple: {} })
+      .accounts({
+        vault: feeVaultPda,
+        authority: authority.publicKey,
+        tokenMint: feeMint.publicKey,
+        tokenVault: feeVaultTokenPda,
+        rewardReserve: feeRewardReservePda,
+        treasury: feeTreasury,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    const depositAmount = 5 * 1000000;
+    const expectedNet = depositAmount - (depositAmount * feeBps) / 10000;
+
+    await program.methods
+      .deposit(new anchor.BN(depositAmount))
+      .accounts({
+        vault: feeVaultPda,
+        userPosition: feeUserPositionPda,
+        user: user.publicKey,
+        tokenMint: feeMint.publicKey,
+        userTokenAccount: feeUserTokenAccount,
+        vaultTokenAccount: feeVaultTokenPda,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([user])
+      .rpc();
+
+    const userPosition = await program.account.userPosition.fetch(feeUserPositionPda);
+    assert.equal(userPosition.depositedAmount.toNumber(), expectedNet);
+
+    const vault = await program.account.vault.fetch(feeVaultPda);
+    const vaultTokens = await getAccount(provider.connection, feeVaultTokenPda, undefined, TOKEN_2022_PROGRAM_ID);
+    assert.equal(vault.totalDeposited.toNumber(), expectedNet);
+    assert.equal(Number(vaultTokens.amount), expectedNet);
+  });
+
+  describe("lock terms", () => {
+    const DAY = 24 * 60 * 60;
+
+    const lockPositionPda = (lockId: number) =>
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("lock-position"),
+          vaultPda.toBuffer(),
+          user.publicKey.toBuffer(),
+          new anchor.BN(lockId).toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0];
+
+    const depositLocked = async (amount: number, termId: number, lockId: number) =>
+      program.methods
+        .depositLocked(new anchor.BN(amount), termId)
+        .accounts({
+          vault: vaultPda,
+          userPosition: userPositionPda,
+          lockPosition: lockPositionPda(lockId),
+          user: user.publicKey,
+          tokenMint: mint,
+          userTokenAccount: userTokenAccount,
+          vaultTokenAccount: vaultTokenPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([user])
+        .rpc();
+
+    const withdrawLocked = async (lockId: number) =>
+      program.methods
+        .withdrawLocked()
+        .accounts({
+          vault: vaultPda,
+          lockPosition: lockPositionPda(lockId),
+          userPosition: userPositionPda,
+          user: user.publicKey,
+          tokenMint: mint,
+          userTokenAccount: userTokenAccount,
+          vaultTokenAccount: vaultTokenPda,
+          rewardReserve: rewardReservePda,
+          treasury: treasuryTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+
+    it("Deposits into a 30-day lock", async () => {
+      await program.methods
+        .setLockTerm(0, new anchor.BN(30 * DAY), new anchor.BN(1200), null)
+        .accounts({ vault: vaultPda, authority: authority.publicKey })
+        .rpc();
+
+      const userPosition = await program.account.userPosition.fetch(userPositionPda);
+      const lockId = userPosition.lockCount.toNumber();
+      const vaultBefore = await program.account.vault.fetch(vaultPda);
+
+      await depositLocked(2 * minDeposit, 0, lockId);
+
+      const lock = await program.account.lockPosition.fetch(lockPositionPda(lockId));
+      assert.equal(lock.amount.toNumber(), 2 * minDeposit);
+      assert.equal(lock.interestRate.toNumber(), 1200);
+      assert.equal(lock.unlockTime.toNumber() - lock.startTime.toNumber(), 30 * DAY);
+
+      // Locked principal stays out of the share pool
+      const vault = await program.account.vault.fetch(vaultPda);
+      assert.equal(vault.totalLocked.toNumber(), vaultBefore.totalLocked.toNumber() + 2 * minDeposit);
+      assert.equal(vault.totalShares.toNumber(), vaultBefore.totalShares.toNumber());
+      // Its fixed interest is set aside in the reserve so share withdrawals cannot spend it
+      assert.isAbove(vault.totalLockedInterest.toNumber(), vaultBefore.totalLockedInterest.toNumber());
+    });
+
+    it("Refuses early withdrawal when the term has no penalty", async () => {
+      const userPosition = await program.account.userPosition.fetch(userPositionPda);
+      try {
+        await withdrawLocked(userPosition.lockCount.toNumber() - 1);
+        assert.fail("Should have failed with lock not matured");
+      } catch (error) {
+        assert.include((error as Error).toString(), "LockNotMatured");
+      }
+    });
+
+    it("Sends the early-withdrawal penalty to the treasury", async () => {
+      const penaltyBps = 500;
+      await program.methods
+        .setLockTerm(1, new anchor.BN(90 * DAY), new anchor.BN(1500), penaltyBps)
+        .accounts({ vault: vaultPda, authority: authority.publicKey })
+        .rpc();
+
+      const userPosition = await program.account.userPosition.fetch(userPositionPda);
+      const lockId = userPosition.lockCount.toNumber();
+      const amount = 2 * minDeposit;
+      const lockedInterestBefore = (await program.account.vault.fetch(vaultPda)).totalLockedInterest.toNumber();
+      await depositLocked(amount, 1, lockId);
+
+      const treasuryBefore = await getAccount(provider.connection, treasuryTokenAccount, undefined, TOKEN_PROGRAM_ID);
+      await withdrawLocked(lockId);
+      const treasuryAfter = await getAccount(provider.connection, treasuryTokenAccount, undefined, TOKEN_PROGRAM_ID);
+
+      assert.equal(
+        Number(treasuryAfter.amount) - Number(treasuryBefore.amount),
+        (amount * penaltyBps) / 10000
+      );
+      assert.isNull(await provider.connection.getAccountInfo(lockPositionPda(lockId)));
+      // The forfeited interest is no longer set aside
+      const vault = await program.account.vault.fetch(vaultPda);
+      assert.equal(vault.totalLockedInterest.toNumber(), lockedInterestBefore);
+    });
+  });
+
+  describe("protocol fees", () => {
+    const noFees = {
+      entryFeeBps: 0,
+      exitFeeBps: 0,
+      performanceFeeBps: 0,
+      managementFeeBps: 0,
+      flashLoanFeeBps: 0,
+    };
+
+    const setFees = async (fees: typeof noFees) =>
+      program.methods
+        .setFees(fees)
+        .accounts({ vault: vaultPda, authority: authority.publicKey })
+        .rpc();
+
+    after(async () => {
+      await setFees(noFees);
+    });
+
+    it("Rejects fees above 100%", async () => {
+      try {
+        await setFees({ ...noFees, exitFeeBps: 10001 });
+        assert.fail("Should have failed with invalid fee");
+      } catch (error) {
+        assert.include((error as Error).toString(), "InvalidFee");
+      }
+    });
+
+    it("Charges the entry fee on deposits", async () => {
+      await setFees({ ...noFees, entryFeeBps: 100 });
+
+      const amount = 2 * minDeposit;
+      const vaultBefore = await program.account.vault.fetch(vaultPda);
+      await program.methods
+        .deposit(new anchor.BN(amount))
+        .accounts({
+          vault: vaultPda,
+          userPosition: userPositionPda,
+          user: user.publicKey,
+          tokenMint: mint,
+          userTokenAccount: userTokenAccount,
+          vaultTokenAccount: vaultTokenPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([user])
+        .rpc();
+      const vault = await program.account.vault.fetch(vaultPda);
+
+      const fee = (amount * 100) / 10000;
+      assert.equal(vault.accruedVaultFees.toNumber() - vaultBefore.accruedVaultFees.toNumber(), fee);
+      assert.equal(vault.totalDeposited.toNumber() - vaultBefore.totalDeposited.toNumber(), amount - fee);
+    });
+
+    it("Sweeps accumulated fees to the treasury", async () => {
+      const vaultBefore = await program.account.vault.fetch(vaultPda);
+      const treasuryBefore = await getAccount(provider.connection, treasuryTokenAccount, undefined, TOKEN_PROGRAM_ID);
+
+      await program.methods
+        .collectFees()
+        .accounts({
+          vault: vaultPda,
+          tokenMint: mint,
+          vaultTokenAccount: vaultTokenPda,
+          rewardReserve: rewardReservePda,
+          treasury: treasuryTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      const vault = await program.account.vault.fetch(vaultPda);
+      const treasuryAfter = await getAccount(provider.connection, treasuryTokenAccount, undefined, TOKEN_PROGRAM_ID);
+      assert.equal(vault.accruedVaultFees.toNumber(), 0);
+      assert.isAtLeast(
+        Number(treasuryAfter.amount) - Number(treasuryBefore.amount),
+        vaultBefore.accruedVaultFees.toNumber()
+      );
+    });
+  });
+
+  it("Closes an emptied position and returns its rent", async () => {
+    const closer = Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(closer.publicKey, anchor.web3.LAMPORTS_PER_SOL)
+    );
+    const closerTokenAccount = await createAccount(
+      provider.connection,
+      authority.payer,
+      mint,
+      closer.publicKey,
+      undefined,
+      undefined,
+      TOKEN_PROGRAM_ID
+    );
+    await mintTo(
+      provider.connection,
+      authority.payer,
+      mint,
+      closerTokenAccount,
+      authority.payer,
+      minDeposit,
+      undefined,
+      undefined,
+      TOKEN_PROGRAM_ID
+    );
+    const [closerPositionPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user-position"), vaultPda.toBuffer(), closer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .deposit(new anchor.BN(minDeposit))
+      .accounts({
+        vault: vaultPda,
+        userPosition: closerPositionPda,
+        user: closer.publicKey,
+        tokenMint: mint,
+        userTokenAccount: closerTokenAccount,
+        vaultTokenAccount: vaultTokenPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([closer])
+      .rpc();
+
+    const closePosition = async () =>
+      program.methods
+        .closePosition()
+        .accounts({
+          vault: vaultPda,
+          userPosition: closerPositionPda,
+          user: closer.publicKey,
+        })
+        .signers([closer])
+        .rpc();
+
+    try {
+      await closePosition();
+      assert.fail("Should have failed with position not empty");
+    } catch (error) {
+      assert.include((error as Error).toString(), "PositionNotEmpty");
+    }
+
+    const position = await program.account.userPosition.fetch(closerPositionPda);
+    const vault = await program.account.vault.fetch(vaultPda);
+    const balance = position.shares
+      .mul(vault.totalDeposited.add(vault.totalAccruedInterest))
+      .div(vault.totalShares);
+    await program.methods
+      .withdraw(balance)
+      .accounts({
+        vault: vaultPda,
+        userPosition: closerPositionPda,
+        user: closer.publicKey,
+        tokenMint: mint,
+        userTokenAccount: closerTokenAccount,
+        vaultTokenAccount: vaultTokenPda,
+        rewardReserve: rewardReservePda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([closer])
+      .rpc();
+
+    const lamportsBefore = await provider.connection.getBalance(closer.publicKey);
+    await closePosition();
+    const lamportsAfter = await provider.connection.getBalance(closer.publicKey);
+
+    assert.isNull(await provider.connection.getAccountInfo(closerPositionPda));
+    assert.isAbove(lamportsAfter, lamportsBefore);
+  });
+
+  it("Runs a second vault for the same mint", async () => {
+    const vaultId = new anchor.BN(1);
+    const idSeed = vaultId.toArrayLike(Buffer, "le", 8);
+    const [lockedVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), mint.toBuffer(), idSeed],
+      program.programId
+    );
+    const [lockedVaultTokenPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault-token"), mint.toBuffer(), idSeed],
+      program.programId
+    );
+    const [lockedReservePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault-reserve"), mint.toBuffer(), idSeed],
+      program.programId
+    );
+
+    await program.methods
+      .initializeVault(vaultId, new anchor.BN(1500), new anchor.BN(minDeposit), { compound: { period: new anchor.BN(86400) } })
+      .accounts({
+        vault: lockedVaultPda,
+        authority: authority.publicKey,
+        tokenMint: mint,
+        tokenVault: lockedVaultTokenPda,
+        rewardReserve: lockedReservePda,
+        treasury: treasuryTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    const lockedVault = await program.account.vault.fetch(lockedVaultPda);
+    const flexibleVault = await program.account.vault.fetch(vaultPda);
+    assert.equal(lockedVault.vaultId.toNumber(), 1);
+    assert.equal(lockedVault.interestRate.toNumber(), 1500);
+    assert.equal(lockedVault.tokenVault.toString(), lockedVaultTokenPda.toString());
+
+    // Vault 0 keeps the original one-vault-per-mint address and is untouched
+    assert.equal(flexibleVault.vaultId.toNumber(), 0);
+    assert.notEqual(flexibleVault.interestRate.toNumber(), 1500);
+  });
+
+  it("Sunsets and closes a vault", async () => {
+    const vaultId = new anchor.BN(2);
+    const idSeed = vaultId.toArrayLike(Buffer, "le", 8);
+    const [sunsetVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), mint.toBuffer(), idSeed],
+      program.programId
+    );
+    const [sunsetVaultTokenPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault-token"), mint.toBuffer(), idSeed],
+      program.programId
+    );
+    const [sunsetReservePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault-reserve"), mint.toBuffer(), idSeed],
+      program.programId
+    );
+    const [sunsetPositionPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user-position"), sunsetVaultPda.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initializeVault(vaultId, new anchor.BN(interestRate), new anchor.BN(minDeposit), { simple: {} })
+      .accounts({
+        vault: sunsetVaultPda,
+        authority: authority.publicKey,
+        tokenMint: mint,
+        tokenVault: sunsetVaultTokenPda,
+        rewardReserve: sunsetReservePda,
+        treasury: treasuryTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    await program.methods
+      .beginSunset(new anchor.BN(7 * 24 * 60 * 60))
+      .accounts({ vault: sunsetVaultPda, authority: authority.publicKey })
+      .rpc();
+
+    try {
+      await program.methods
+        .deposit(new anchor.BN(minDeposit))
+        .accounts({
+          vault: sunsetVaultPda,
+          userPosition: sunsetPositionPda,
+          user: user.publicKey,
+          tokenMint: mint,
+          userTokenAccount: userTokenAccount,
+          vaultTokenAccount: sunsetVaultTokenPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([user])
+        .rpc();
+      assert.fail("Should have failed with vault sunsetting");
+    } catch (error) {
+      assert.include((error as Error).toString(), "VaultSunsetting");
+    }
+
+    // Nobody is left, so the vault can close before the deadline
+    await program.methods
+      .closeVault()
+      .accounts({
+        vault: sunsetVaultPda,
+        authority: authority.publicKey,
+        tokenMint: mint,
+        authorityTokenAccount: authorityTokenAccount,
+        vaultTokenAccount: sunsetVaultTokenPda,
+        rewardReserve: sunsetReservePda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    assert.isNull(await provider.connection.getAccountInfo(sunsetVaultPda));
+    assert.isNull(await provider.connection.getAccountInfo(sunsetVaultTokenPda));
+    assert.isNull(await provider.connection.getAccountInfo(sunsetReservePda));
+  });
+
+  it("Refuses to close a vault that is not sunsetting", async () => {
+    try {
+      await program.methods
+        .closeVault()
+        .accounts({
+          vault: vaultPda,
+          authority: authority.publicKey,
+          tokenMint: mint,
+          authorityTokenAccount: authorityTokenAccount,
+          vaultTokenAccount: vaultTokenPda,
+          rewardReserve: rewardReservePda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      assert.fail("Should have failed with vault not sunsetting");
+    } catch (error) {
+      assert.include((error as Error).toString(), "VaultNotSunsetting");
+    }
+  });
+
+  it("Enforces the total and per-user deposit caps", async () => {
+    const setCaps = async (maxTotal: anchor.BN | null, maxPerUser: anchor.BN | null) =>
+      program.methods
+        .setDepositCaps(maxTotal, maxPerUser)
+        .accounts({ vault: vaultPda, authority: authority.publicKey })
+        .rpc();
+
+    const deposit = async () =>
+      program.methods
+        .deposit(new anchor.BN(minDeposit))
+        .accounts({
+          vault: vaultPda,
+          userPosition: userPositionPda,
+          user: user.publicKey,
+          tokenMint: mint,
+          userTokenAccount: userTokenAccount,
+          vaultTokenAccount: vaultTokenPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([user])
+        .rpc();
+
+    const vault = await program.account.vault.fetch(vaultPda);
+    const position = await program.account.userPosition.fetch(userPositionPda);
+
+    await setCaps(null, position.depositedAmount);
+    try {
+      await deposit();
+      assert.fail("Should have failed with user deposit cap exceeded");
+    } catch (error) {
+      assert.include((error as Error).toString(), "UserDepositCapExceeded");
+    }
+
+    // Locked principal counts towards the per-user cap, so locking cannot get around it
+    await setCaps(null, position.depositedAmount.add(position.lockedAmount));
+    const [lockPositionPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("lock-position"),
+        vaultPda.toBuffer(),
+        user.publicKey.toBuffer(),
+        position.lockCount.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    try {
+      await program.methods
+        .depositLocked(new anchor.BN(minDeposit), 0)
+        .accounts({
+          vault: vaultPda,
+          userPosition: userPositionPda,
+          lockPosition: lockPositionPda,
+          user: user.publicKey,
+          tokenMint: mint,
+          userTokenAccount: userTokenAccount,
+          vaultTokenAccount: vaultTokenPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([user])
+        .rpc();
+      assert.fail("Should have failed with user deposit cap exceeded");
+    } catch (error) {
+      assert.include((error as Error).toString(), "UserDepositCapExceeded");
+    }
+
+    await setCaps(vault.totalDeposited.add(vault.totalLocked), null);
+    try {
+      await deposit();
+      assert.fail("Should have failed with deposit cap exceeded");
+    } catch (error) {
+      assert.include((error as Error).toString(), "DepositCapExceeded");
+    }
+
+    await setCaps(null, null);
+    await deposit();
+  });
+
+  it("Queues withdrawals behind the cooldown", async () => {
+    const [pendingWithdrawalPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pending-withdrawal"), vaultPda.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+    const setCooldown = async (seconds: number) =>
+      program.methods
+        .setWithdrawalCooldown(new anchor.BN(seconds))
+        .accounts({ vault: vaultPda, authority: authority.publicKey })
+        .rpc();
+    const claim = async () =>
+      program.methods
+        .claimWithdraw()
+        .accounts({
+          vault: vaultPda,
+          pendingWithdrawal: pendingWithdrawalPda,
+          user: user.publicKey,
+          tokenMint: mint,
+          userTokenAccount: userTokenAccount,
+          vaultTokenAccount: vaultTokenPda,
+          rewardReserve: rewardReservePda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+
+    await setCooldown(2);
+
+    try {
+      await program.methods
+        .withdraw(new anchor.BN(minDeposit))
+        .accounts({
+          vault: vaultPda,
+          userPosition: userPositionPda,
+          user: user.publicKey,
+          tokenMint: mint,
+          userTokenAccount: userTokenAccount,
+          vaultTokenAccount: vaultTokenPda,
+          rewardReserve: rewardReservePda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+      assert.fail("Should have failed with withdrawal cooldown active");
+    } catch (error) {
+      assert.include((error as Error).toString(), "WithdrawalCooldownActive");
+    }
+
+    const sharesBefore = (await program.account.userPosition.fetch(userPositionPda)).shares;
+    await program.methods
+      .requestWithdraw(new anchor.BN(minDeposit))
+      .accounts({
+        vault: vaultPda,
+        userPosition: userPositionPda,
+        pendingWithdrawal: pendingWithdrawalPda,
+        user: user.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user])
+      .rpc();
+
+    // Shares are burned at request time, so the queued amount stops earning
+    const position = await program.account.userPosition.fetch(userPositionPda);
+    const pending = await program.account.pendingWithdrawal.fetch(pendingWithdrawalPda);
+    assert.isTrue(position.shares.lt(sharesBefore));
+    assert.equal(pending.principalAmount.add(pending.interestAmount).toNumber(), minDeposit);
+
+    try {
+      await claim();
+      assert.fail("Should have failed with withdrawal not claimable");
+    } catch (error) {
+      assert.include((error as Error).toString(), "WithdrawalNotClaimable");
+    }
+
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+    const balanceBefore = await getAccount(provider.connection, userTokenAccount, undefined, TOKEN_PROGRAM_ID);
+    await claim();
+    const balanceAfter = await getAccount(provider.connection, userTokenAccount, undefined, TOKEN_PROGRAM_ID);
+
+    assert.equal(Number(balanceAfter.amount) - Number(balanceBefore.amount), minDeposit);
+    assert.isNull(await provider.connection.getAccountInfo(pendingWithdrawalPda));
+
+    await setCooldown(0);
+  });
+
+  it("Throttles outflows and lets the guardian only tighten the limit", async () => {
+    const guardian = Keypair.generate();
+    const setOutflowLimit = async (limit: { window: anchor.BN; maxAmount: anchor.BN | null; maxTvlBps: number | null }, signer?: Keypair) =>
+      program.methods
+        .setOutflowLimit(limit)
+        .accounts({ vault: vaultPda, signer: signer ? signer.publicKey : authority.publicKey })
+        .signers(signer ? [signer] : [])
+        .rpc();
+
+    await program.methods
+      .setGuardian(guardian.publicKey)
+      .accounts({ vault: vaultPda, authority: authority.publicKey })
+      .rpc();
+
+    const hour = new anchor.BN(60 * 60);
+    await setOutflowLimit({ window: hour, maxAmount: new anchor.BN(minDeposit), maxTvlBps: null });
+
+    try {
+      await program.methods
+        .withdraw(new anchor.BN(2 * minDeposit))
+        .accounts({
+          vault: vaultPda,
+          userPosition: userPositionPda,
+          user: user.publicKey,
+          tokenMint: mint,
+          userTokenAccount: userTokenAccount,
+          vaultTokenAccount: vaultTokenPda,
+          rewardReserve: rewardReservePda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+      assert.fail("Should have failed with outflow limit exceeded");
+    } catch (error) {
+      assert.include((error as Error).toString(), "OutflowLimitExceeded");
+    }
+
+    try {
+      await setOutflowLimit({ window: hour, maxAmount: new anchor.BN(10 * minDeposit), maxTvlBps: null }, guardian);
+      assert.fail("Should have failed with guardian cannot loosen");
+    } catch (error) {
+      assert.include((error as Error).toString(), "GuardianCannotLoosen");
+    }
+
+    await setOutflowLimit({ window: hour, maxAmount: new anchor.BN(minDeposit / 2), maxTvlBps: 100 }, guardian);
+    const vault = await program.account.vault.fetch(vaultPda);
+    assert.equal(vault.outflowLimit.maxAmount.toNumber(), minDeposit / 2);
+    assert.equal(vault.outflowLimit.maxTvlBps, 100);
+
+    await setOutflowLimit({ window: new anchor.BN(0), maxAmount: null, maxTvlBps: null });
+  });
+
+  it("Derives the rate from a kinked utilization curve", async () => {
+    const curve = {
+      baseRateBps: new anchor.BN(200),
+      slope1Bps: new anchor.BN(400),
+      optimalUtilizationBps: new anchor.BN(8000),
+      slope2Bps: new anchor.BN(6000),
+    };
+
+    try {
+      await program.methods
+        .setRateCurve({ ...curve, optimalUtilizationBps: new anchor.BN(0) })
+        .accounts({ vault: vaultPda, authority: authority.publicKey })
+        .rpc();
+      assert.fail("Should have failed with invalid rate curve");
+    } catch (error) {
+      assert.include((error as Error).toString(), "InvalidRateCurve");
+    }
+
+    await program.methods
+      .setRateCurve(curve)
+      .accounts({ vault: vaultPda, authority: authority.publicKey })
+      .rpc();
+
+    // Nothing is borrowed, so borrowers would pay the base rate and depositors earn nothing
+    let vault = await program.account.vault.fetch(vaultPda);
+    assert.equal(vault.borrowRate.toNumber(), 200);
+    assert.equal(vault.interestRate.toNumber(), 0);
+
+    await program.methods
+      .setRateCurve(null)
+      .accounts({ vault: vaultPda, authority: authority.publicKey })
+      .rpc();
+    await program.methods
+      .updateVaultParams(new anchor.BN(interestRate), new anchor.BN(minDeposit), { simple: {} })
+      .accounts({ vault: vaultPda, authority: authority.publicKey })
+      .rpc();
+
+    vault = await program.account.vault.fetch(vaultPda);
+    assert.isNull(vault.rateCurve);
+    assert.equal(vault.interestRate.toNumber(), interestRate);
+  });
+
+  it("Borrows against a position in another vault and repays", async () => {
+    const vaultId = new anchor.BN(3);
+    const idSeed = vaultId.toArrayLike(Buffer, "le", 8);
+    const [lendVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), mint.toBuffer(), idSeed],
+      program.programId
+    );
+    const [lendVaultTokenPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault-token"), mint.toBuffer(), idSeed],
+      program.programId
+    );
+    const [lendReservePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault-reserve"), mint.toBuffer(), idSeed],
+      program.programId
+    );
+    const [lendPositionPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user-position"), lendVaultPda.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+    const [borrowPositionPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("borrow-position"), lendVaultPda.toBuffer(), vaultPda.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+    const [collateralConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("collateral-config"), lendVaultPda.toBuffer(), vaultPda.toBuffer()],
+      program.programId
+    );
+    const setCollateralConfig = (enabled: boolean) =>
+      program.methods
+        .setCollateralConfig(enabled)
+        .accounts({
+          borrowVault: lendVaultPda,
+          collateralVault: vaultPda,
+          collateralConfig: collateralConfigPda,
+          priceFeed: null,
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+    await program.methods
+      .initializeVault(vaultId, new anchor.BN(interestRate), new anchor.BN(minDeposit), { simple: {} })
+      .accounts({
+        vault: lendVaultPda,
+        authority: authority.publicKey,
+        tokenMint: mint,
+        tokenVault: lendVaultTokenPda,
+        rewardReserve: lendReservePda,
+        treasury: treasuryTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+    await program.methods
+      .deposit(new anchor.BN(2 * minDeposit))
+      .accounts({
+        vault: lendVaultPda,
+        userPosition: lendPositionPda,
+        user: user.publicKey,
+        tokenMint: mint,
+        userTokenAccount: userTokenAccount,
+        vaultTokenAccount: lendVaultTokenPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([user])
+      .rpc();
+
+    const borrowConfig = {
+      maxLtvBps: 5000,
+      liquidationThresholdBps: 8000,
+      liquidationBonusBps: 500,
+      closeFactorBps: 5000,
+    };
+    const borrow = (collateralShares: number, amount: number) =>
+      program.methods
+        .borrow(new anchor.BN(collateralShares), new anchor.BN(amount))
+        .accounts({
+          borrowVault: lendVaultPda,
+          collateralVault: vaultPda,
+          collateralConfig: collateralConfigPda,
+          userPosition: userPositionPda,
+          borrowPosition: borrowPositionPda,
+          user: user.publicKey,
+          tokenMint: mint,
+          userTokenAccount: userTokenAccount,
+          vaultTokenAccount: lendVaultTokenPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          collateralPriceFeed: null,
+          borrowPriceFeed: null,
+        })
+        .signers([user])
+        .rpc();
+
+    await setCollateralConfig(true);
+    try {
+      await borrow(minDeposit, minDeposit / 4);
+      assert.fail("Should have failed with borrowing disabled");
+    } catch (error) {
+      assert.include((error as Error).toString(), "BorrowingDisabled");
+    }
+
+    await program.methods
+      .setBorrowConfig(borrowConfig)
+      .accounts({ vault: lendVaultPda, authority: authority.publicKey })
+      .rpc();
+
+    try {
+      await borrow(minDeposit, minDeposit);
+      assert.fail("Should have failed with max LTV exceeded");
+    } catch (error) {
+      assert.include((error as Error).toString(), "ExceedsMaxLtv");
+    }
+
+    // The lending vault's authority decides which vaults it lends against
+    await setCollateralConfig(false);
+    try {
+      await borrow(minDeposit, minDeposit / 4);
+      assert.fail("Should have failed with the collateral not accepted");
+    } catch (error) {
+      assert.include((error as Error).toString(), "CollateralNotAccepted");
+    }
+    await setCollateralConfig(true);
+
+    const sharesBefore = (await program.account.userPosition.fetch(userPositionPda)).shares.toNumber();
+    await borrow(minDeposit, minDeposit / 4);
+
+    let position = await program.account.borrowPosition.fetch(borrowPositionPda);
+    assert.equal(position.collateralShares.toNumber(), minDeposit);
+    assert.equal(position.principal.toNumber(), minDeposit / 4);
+    let collateralPosition = await program.account.userPosition.fetch(userPositionPda);
+    assert.equal(collateralPosition.shares.toNumber(), sharesBefore - minDeposit);
+    let lendVault = await program.account.vault.fetch(lendVaultPda);
+    assert.equal(lendVault.totalBorrowed.toNumber(), minDeposit / 4);
+    // The collateral vault knows its shares secure a loan, so it cannot close under it
+    let collateralVault = await program.account.vault.fetch(vaultPda);
+    assert.equal(collateralVault.totalCollateralShares.toNumber(), minDeposit);
+
+    // Over-asking repays exactly the outstanding debt
+    await program.methods
+      .repay(new anchor.BN(minDeposit))
+      .accounts({
+        borrowVault: lendVaultPda,
+        borrowPosition: borrowPositionPda,
+        user: user.publicKey,
+        tokenMint: mint,
+        userTokenAccount: userTokenAccount,
+        vaultTokenAccount: lendVaultTokenPda,
+        rewardReserve: lendReservePda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    position = await program.account.borrowPosition.fetch(borrowPositionPda);
+    assert.equal(position.debt.toNumber(), 0);
+    lendVault = await program.account.vault.fetch(lendVaultPda);
+    assert.equal(lendVault.totalBorrowed.toNumber(), 0);
+
+    await program.methods
+      .releaseCollateral(new anchor.BN(minDeposit))
+      .accounts({
+        borrowVault: lendVaultPda,
+        collateralVault: vaultPda,
+        collateralConfig: collateralConfigPda,
+        borrowPosition: borrowPositionPda,
+        userPosition: userPositionPda,
+        user: user.publicKey,
+        systemProgram: SystemProgram.programId,
+        collateralPriceFeed: null,
+        borrowPriceFeed: null,
+      })
+      .signers([user])
+      .rpc();
+
+    collateralPosition = await program.account.userPosition.fetch(userPositionPda);
+    assert.equal(collateralPosition.shares.toNumber(), sharesBefore);
+    collateralVault = await program.account.vault.fetch(vaultPda);
+    assert.equal(collateralVault.totalCollateralShares.toNumber(), 0);
+
+    // Borrow again, then drop the threshold under the position to make it liquidatable
+    await borrow(minDeposit, minDeposit / 4);
+    const [liquidatorPositionPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user-position"), vaultPda.toBuffer(), authority.publicKey.toBuffer()],
+      program.programId
+    );
+    const liquidate = (repayAmount: number) =>
+      program.methods
+        .liquidate(new anchor.BN(repayAmount))
+        .accounts({
+          borrowVault: lendVaultPda,
+          collateralVault: vaultPda,
+          collateralConfig: collateralConfigPda,
+          borrowPosition: borrowPositionPda,
+          borrower: user.publicKey,
+          liquidatorPosition: liquidatorPositionPda,
+          liquidator: authority.publicKey,
+          tokenMint: mint,
+          liquidatorTokenAccount: authorityTokenAccount,
+          vaultTokenAccount: lendVaultTokenPda,
+          rewardReserve: lendReservePda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          collateralPriceFeed: null,
+          borrowPriceFeed: null,
+        })
+        .rpc();
+
+    try {
+      await liquidate(minDeposit / 10);
+      assert.fail("Should have failed with a healthy position");
+    } catch (error) {
+      assert.include((error as Error).toString(), "PositionHealthy");
+    }
+
+    await program.methods
+      .setBorrowConfig({ ...borrowConfig, maxLtvBps: 1000, liquidationThresholdBps: 1000 })
+      .accounts({ vault: lendVaultPda, authority: authority.publicKey })
+      .rpc();
+
+    // The close factor caps this at half the debt
+    const debtBefore = (await program.account.borrowPosition.fetch(borrowPositionPda)).debt.toNumber();
+    await liquidate(minDeposit);
+
+    position = await program.account.borrowPosition.fetch(borrowPositionPda);
+    const repaid = debtBefore - position.debt.toNumber();
+    assert.isAtMost(repaid, Math.ceil(debtBefore / 2));
+    const seized = minDeposit - position.collateralShares.toNumber();
+    assert.isAbove(seized, repaid);
+    const liquidatorPosition = await program.account.userPosition.fetch(liquidatorPositionPda);
+    assert.equal(liquidatorPosition.shares.toNumber(), seized);
+    collateralVault = await program.account.vault.fetch(vaultPda);
+    assert.equal(collateralVault.totalCollateralShares.toNumber(), position.collateralShares.toNumber());
+  });
+
+  it("Liquidates collateral in another mint at price feed prices", async () => {
+    const lendIdSeed = new anchor.BN(3).toArrayLike(Buffer, "le", 8);
+    const [lendVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), mint.toBuffer(), lendIdSeed],
+      program.programId
+    );
+    const [lendVaultTokenPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault-token"), mint.toBuffer(), lendIdSeed],
+      program.programId
+    );
+    const [lendReservePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault-reserve"), mint.toBuffer(), lendIdSeed],
+      program.programId
+    );
+
+    const collateralMint = await createMint(
+      provider.connection,
+      authority.payer,
+      authority.publicKey,
+      null,
+      6,
+      undefined,
+      undefined,
+      TOKEN_PROGRAM_ID
+    );
+    const userCollateralAccount = await createAccount(
+      provider.connection,
+      authority.payer,
+      collateralMint,
+      user.publicKey,
+      undefined,
+      undefined,
+      TOKEN_PROGRAM_ID
+    );
+    const collateralTreasury = await createAccount(
+      provider.connection,
+      authority.payer,
+      collateralMint,
+      authority.publicKey,
+      undefined,
+      undefined,
+      TOKEN_PROGRAM_ID
+    );
+    await mintTo(provider.connection, authority.payer, collateralMint, userCollateralAccount, authority.payer, 10 * minDeposit);
+
+    const [collateralVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), collateralMint.toBuffer()],
+      program.programId
+    );
+    const [collateralVaultTokenPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault-token"), collateralMint.toBuffer()],
+      program.programId
+    );
+    const [collateralReservePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault-reserve"), collateralMint.toBuffer()],
+      program.programId
+    );
+    const [collateralPositionPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user-position"), collateralVaultPda.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+    const [liquidatorPositionPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user-position"), collateralVaultPda.toBuffer(), authority.publicKey.toBuffer()],
+      program.programId
+    );
+    const [borrowPositionPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("borrow-position"), lendVaultPda.toBuffer(), collateralVaultPda.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+    const [collateralConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("collateral-config"), lendVaultPda.toBuffer(), collateralVaultPda.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initializeVault(new anchor.BN(0), new anchor.BN(interestRate), new anchor.BN(minDeposit), { simple: {} })
+      .accounts({
+        vault: collateralVaultPda,
+        authority: authority.publicKey,
+        tokenMint: collateralMint,
+        tokenVault: collateralVaultTokenPda,
+        rewardReserve: collateralReservePda,
+        treasury: collateralTreasury,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+    await program.methods
+      .deposit(new anchor.BN(2 * minDeposit))
+      .accounts({
+        vault: collateralVaultPda,
+        userPosition: collateralPositionPda,
+        user: user.publicKey,
+        tokenMint: collateralMint,
+        userTokenAccount: userCollateralAccount,
+        vaultTokenAccount: collateralVaultTokenPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([user])
+      .rpc();
+
+    const priceFeedPda = (feedMint: PublicKey, feedAuthority: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("price-feed"), feedMint.toBuffer(), feedAuthority.toBuffer()],
+        program.programId
+      )[0];
+    const initializePriceFeed = (feedMint: PublicKey, price: number, feedAuthority: Keypair | null) =>
+      program.methods
+        .initializePriceFeed(new anchor.BN(price))
+        .accounts({
+          priceFeed: priceFeedPda(feedMint, feedAuthority?.publicKey ?? authority.publicKey),
+          tokenMint: feedMint,
+          authority: feedAuthority?.publicKey ?? authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers(feedAuthority ? [feedAuthority] : [])
+        .rpc();
+    const borrowFeed = priceFeedPda(mint, authority.publicKey);
+    const collateralFeed = priceFeedPda(collateralMint, authority.publicKey);
+
+    await initializePriceFeed(mint, 1_000_000, null);
+    await initializePriceFeed(collateralMint, 2_000_000, null);
+
+    const setCollateralConfig = (priceFeed: PublicKey) =>
+      program.methods
+        .setCollateralConfig(true)
+        .accounts({
+          borrowVault: lendVaultPda,
+          collateralVault: collateralVaultPda,
+          collateralConfig: collateralConfigPda,
+          priceFeed,
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+    // A feed pushed by anyone but the lending vault's authority would let them set its limits
+    await initializePriceFeed(collateralMint, 2_000_000, user);
+    try {
+      await setCollateralConfig(priceFeedPda(collateralMint, user.publicKey));
+      assert.fail("Should have failed with an invalid price feed");
+    } catch (error) {
+      assert.include((error as Error).toString(), "InvalidPriceFeed");
+    }
+
+    await program.methods
+      .setPriceFeed()
+      .accounts({ vault: lendVaultPda, priceFeed: borrowFeed, authority: authority.publicKey })
+      .rpc();
+    await setCollateralConfig(collateralFeed);
+
+    // Two tokens of collateral at 2.0 against a quarter token of debt at 1.0
+    await program.methods
+      .borrow(new anchor.BN(2 * minDeposit), new anchor.BN(minDeposit / 4))
+      .accounts({
+        borrowVault: lendVaultPda,
+        collateralVault: collateralVaultPda,
+        collateralConfig: collateralConfigPda,
+        userPosition: collateralPositionPda,
+        borrowPosition: borrowPositionPda,
+        user: user.publicKey,
+        tokenMint: mint,
+        userTokenAccount: userTokenAccount,
+        vaultTokenAccount: lendVaultTokenPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        collateralPriceFeed: collateralFeed,
+        borrowPriceFeed: borrowFeed,
+      })
+      .signers([user])
+      .rpc();
+
+    const liquidate = (withFeeds: boolean) =>
+      program.methods
+        .liquidate(new anchor.BN(minDeposit))
+        .accounts({
+          borrowVault: lendVaultPda,
+          collateralVault: collateralVaultPda,
+          collateralConfig: collateralConfigPda,
+          borrowPosition: borrowPositionPda,
+          borrower: user.publicKey,
+          liquidatorPosition: liquidatorPositionPda,
+          liquidator: authority.publicKey,
+          tokenMint: mint,
+          liquidatorTokenAccount: authorityTokenAccount,
+          vaultTokenAccount: lendVaultTokenPda,
+          rewardReserve: lendReservePda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          collateralPriceFeed: withFeeds ? collateralFeed : null,
+          borrowPriceFeed: withFeeds ? borrowFeed : null,
+        })
+        .rpc();
+
+    try {
+      await liquidate(false);
+      assert.fail("Should have failed with a missing price feed");
+    } catch (error) {
+      assert.include((error as Error).toString(), "MissingPriceFeed");
+    }
+    try {
+      await liquidate(true);
+      assert.fail("Should have failed with a healthy position");
+    } catch (error) {
+      assert.include((error as Error).toString(), "PositionHealthy");
+    }
+
+    // After a hand-off the old authority's feeds no longer price anything
+    const newAuthority = Keypair.generate();
+    await program.methods
+      .proposeAuthority(newAuthority.publicKey)
+      .accounts({ vault: lendVaultPda, authority: authority.publicKey })
+      .rpc();
+    await program.methods
+      .acceptAuthority()
+      .accounts({ vault: lendVaultPda, pendingAuthority: newAuthority.publicKey })
+      .signers([newAuthority])
+      .rpc();
+    try {
+      await liquidate(true);
+      assert.fail("Should have failed with an invalid price feed");
+    } catch (error) {
+      assert.include((error as Error).toString(), "InvalidPriceFeed");
+    }
+    await program.methods
+      .proposeAuthority(authority.publicKey)
+      .accounts({ vault: lendVaultPda, authority: newAuthority.publicKey })
+      .signers([newAuthority])
+      .rpc();
+    await program.methods
+      .acceptAuthority()
+      .accounts({ vault: lendVaultPda, pendingAuthority: authority.publicKey })
+      .rpc();
+
+    // Halving the collateral price pushes the debt past the 10% liquidation threshold
+    await program.methods
+      .updatePrice(new anchor.BN(1_000_000))
+      .accounts({ priceFeed: collateralFeed, authority: authority.publicKey })
+      .rpc();
+
+    const debtBefore = (await program.account.borrowPosition.fetch(borrowPositionPda)).debt.toNumber();
+    await liquidate(true);
+
+    const position = await program.account.borrowPosition.fetch(borrowPositionPda);
+    const repaid = debtBefore - position.debt.toNumber();
+    assert.isAbove(repaid, 0);
+    // At equal prices the seized collateral is the repaid amount plus the 5% bonus
+    const seized = 2 * minDeposit - position.collateralShares.toNumber();
+    assert.approximately(seized, Math.floor(repaid * 1.05), 2);
+    const liquidatorPosition = await program.account.userPosition.fetch(liquidatorPositionPda);
+    assert.equal(liquidatorPosition.shares.toNumber(), seized);
+  });
+
+  describe("reward streams", () => {
+    const vaultId = new anchor.BN(4);
+    const idSeed = vaultId.toArrayLike(Buffer, "le", 8);
+    let streamVaultPda: PublicKey;
+    let streamVaultTokenPda: PublicKey;
+    let streamReservePda: PublicKey;
+    let streamPositionPda: PublicKey;
+    const streamPda = (index: number, vault = streamVaultPda) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("reward-stream"), vault.toBuffer(), Buffer.from([index])],
+        program.programId
+      )[0];
+    const escrowPda = (index: number, vault = streamVaultPda) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("reward-escrow"), streamPda(index, vault).toBuffer()],
+        program.programId
+      )[0];
+
+    const emissionRate = 1000;
+    const duration = 3600;
+    const rewardMints: PublicKey[] = [];
+    const authorityRewardAccounts: PublicKey[] = [];
+    const userRewardAccounts: PublicKey[] = [];
+
+    const balanceOf = async (account: PublicKey) =>
+      Number((await getAccount(provider.connection, account, undefined, TOKEN_PROGRAM_ID)).amount);
+
+    const addStream = async (index: number, vault = streamVaultPda, streamDuration = duration) => {
+      const rewardMint = await createMint(
+        provider.connection,
+        authority.payer,
+        authority.publicKey,
+        null,
+        6,
+        undefined,
+        undefined,
+        TOKEN_PROGRAM_ID
+      );
+      const authorityRewardAccount = await createAccount(
+        provider.connection,
+        authority.payer,
+        rewardMint,
+        authority.publicKey,
+        undefined,
+        undefined,
+        TOKEN_PROGRAM_ID
+      );
+      const userRewardAccount = await createAccount(
+        provider.connection,
+        authority.payer,
+        rewardMint,
+        user.publicKey,
+        undefined,
+        undefined,
+        TOKEN_PROGRAM_ID
+      );
+      // Enough for the stream and one extension of the same length
+      await mintTo(
+        provider.connection,
+        authority.payer,
+        rewardMint,
+        authorityRewardAccount,
+        authority.payer,
+        2 * emissionRate * duration,
+        undefined,
+        undefined,
+        TOKEN_PROGRAM_ID
+      );
+      rewardMints.push(rewardMint);
+      authorityRewardAccounts.push(authorityRewardAccount);
+      userRewardAccounts.push(userRewardAccount);
+
+      const now = Math.floor(Date.now() / 1000);
+      await program.methods
+        .addRewardStream(index, new anchor.BN(emissionRate), new anchor.BN(now), new anchor.BN(now + streamDuration))
+        .accounts({
+          vault,
+          rewardStream: streamPda(index, vault),
+          rewardMint: rewardMint,
+          rewardEscrow: escrowPda(index, vault),
+          authorityRewardAccount: authorityRewardAccount,
+          authority: authority.publicKey,
+          rewardTokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .rpc();
+    };
+
+    const deposit = (streams: PublicKey[]) =>
+      program.methods
+        .deposit(new anchor.BN(minDeposit))
+        .accounts({
+          vault: streamVaultPda,
+          userPosition: streamPositionPda,
+          user: user.publicKey,
+          tokenMint: mint,
+          userTokenAccount: userTokenAccount,
+          vaultTokenAccount: streamVaultTokenPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .remainingAccounts(streams.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false })))
+        .signers([user])
+        .rpc();
+
+    before(async () => {
+      [streamVaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), mint.toBuffer(), idSeed],
+        program.programId
+      );
+      [streamVaultTokenPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault-token"), mint.toBuffer(), idSeed],
+        program.programId
+      );
+      [streamReservePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault-reserve"), mint.toBuffer(), idSeed],
+        program.programId
+      );
+      [streamPositionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user-position"), streamVaultPda.toBuffer(), user.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .initializeVault(vaultId, new anchor.BN(interestRate), new anchor.BN(minDeposit), { simple: {} })
+        .accounts({
+          vault: streamVaultPda,
+          authority: authority.publicKey,
+          tokenMint: mint,
+          tokenVault: streamVaultTokenPda,
+          rewardReserve: streamReservePda,
+          treasury: treasuryTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .rpc();
+    });
+
+    it("Streams a secondary reward token to depositors", async () => {
+      await addStream(0);
+      assert.equal(await balanceOf(escrowPda(0)), emissionRate * duration);
+
+      // Skipping the stream would settle rewards at a stale index
+      try {
+        await deposit([]);
+        assert.fail("Should have failed without the reward stream");
+      } catch (error) {
+        assert.include((error as Error).toString(), "InvalidRewardStream");
+      }
+
+      await deposit([streamPda(0)]);
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+
+      await program.methods
+        .claimRewards()
+        .accounts({
+          vault: streamVaultPda,
+          userPosition: streamPositionPda,
+          rewardStream: streamPda(0),
+          rewardMint: rewardMints[0],
+          rewardEscrow: escrowPda(0),
+          userRewardAccount: userRewardAccounts[0],
+          user: user.publicKey,
+          rewardTokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+
+      // The only shareholder earns the whole emission, less rounding
+      const claimed = await balanceOf(userRewardAccounts[0]);
+      assert.isAbove(claimed, 0);
+      assert.isAtMost(claimed, emissionRate * duration);
+      const position = await program.account.userPosition.fetch(streamPositionPda);
+      assert.equal(position.pendingRewards[0].toNumber(), 0);
+    });
+
+    it("Runs a partner stream alongside and claims both at once", async () => {
+      try {
+        await addStream(2);
+        assert.fail("Should have failed with a slot that is not the first free one");
+      } catch (error) {
+        assert.include((error as Error).toString(), "InvalidRewardStream");
+      }
+      await addStream(1);
+
+      const stream = await program.account.rewardStream.fetch(streamPda(0));
+      const newEndTime = stream.endTime.toNumber() + duration;
+      await program.methods
+        .extendRewardStream(new anchor.BN(newEndTime))
+        .accounts({
+          vault: streamVaultPda,
+          rewardStream: streamPda(0),
+          rewardMint: rewardMints[0],
+          rewardEscrow: escrowPda(0),
+          authorityRewardAccount: authorityRewardAccounts[0],
+          authority: authority.publicKey,
+          rewardTokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      assert.equal((await program.account.rewardStream.fetch(streamPda(0))).endTime.toNumber(), newEndTime);
+
+      // Share changes now need both streams, in slot order
+      await deposit([streamPda(0), streamPda(1)]);
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+
+      const before = await Promise.all(userRewardAccounts.map(balanceOf));
+      await program.methods
+        .claimAllRewards()
+        .accounts({
+          vault: streamVaultPda,
+          userPosition: streamPositionPda,
+          user: user.publicKey,
+        })
+        .remainingAccounts(
+          [0, 1].flatMap((index) => [
+            { pubkey: streamPda(index), isWritable: true, isSigner: false },
+            { pubkey: rewardMints[index], isWritable: false, isSigner: false },
+            { pubkey: escrowPda(index), isWritable: true, isSigner: false },
+            { pubkey: userRewardAccounts[index], isWritable: true, isSigner: false },
+            { pubkey: TOKEN_PROGRAM_ID, isWritable: false, isSigner: false },
+          ])
+        )
+        .signers([user])
+        .rpc();
+
+      const after = await Promise.all(userRewardAccounts.map(balanceOf));
+      assert.isAbove(after[0], before[0]);
+      assert.isAbove(after[1], before[1]);
+    });
+
+    it("Gives no emissions to shares escrowed as collateral", async () => {
+      const lendIdSeed = new anchor.BN(3).toArrayLike(Buffer, "le", 8);
+      const [lendVaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), mint.toBuffer(), lendIdSeed],
+        program.programId
+      );
+      const [lendVaultTokenPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault-token"), mint.toBuffer(), lendIdSeed],
+        program.programId
+      );
+      const [lendReservePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault-reserve"), mint.toBuffer(), lendIdSeed],
+        program.programId
+      );
+      const [collateralConfigPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("collateral-config"), lendVaultPda.toBuffer(), streamVaultPda.toBuffer()],
+        program.programId
+      );
+      const [borrowPositionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("borrow-position"), lendVaultPda.toBuffer(), streamVaultPda.toBuffer(), user.publicKey.toBuffer()],
+        program.programId
+      );
+      const streams = [streamPda(0), streamPda(1)].map((pubkey) => ({ pubkey, isWritable: true, isSigner: false }));
+
+      await program.methods
+        .setCollateralConfig(true)
+        .accounts({
+          borrowVault: lendVaultPda,
+          collateralVault: streamVaultPda,
+          collateralConfig: collateralConfigPda,
+          priceFeed: null,
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      // Escrow every share the vault has, so nothing is left to earn emissions
+      const shares = (await program.account.userPosition.fetch(streamPositionPda)).shares.toNumber();
+      await program.methods
+        .borrow(new anchor.BN(shares), new anchor.BN(1))
+        .accounts({
+          borrowVault: lendVaultPda,
+          collateralVault: streamVaultPda,
+          collateralConfig: collateralConfigPda,
+          userPosition: streamPositionPda,
+          borrowPosition: borrowPositionPda,
+          user: user.publicKey,
+          tokenMint: mint,
+          userTokenAccount: userTokenAccount,
+          vaultTokenAccount: lendVaultTokenPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          collateralPriceFeed: null,
+          borrowPriceFeed: null,
+        })
+        .remainingAccounts(streams)
+        .signers([user])
+        .rpc();
+      const unclaimedBefore = (await program.account.rewardStream.fetch(streamPda(0))).unclaimedRewards.toNumber();
+      const pendingBefore = (await program.account.userPosition.fetch(streamPositionPda)).pendingRewards[0].toNumber();
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+
+      await program.methods
+        .repay(new anchor.BN(minDeposit))
+        .accounts({
+          borrowVault: lendVaultPda,
+          borrowPosition: borrowPositionPda,
+          user: user.publicKey,
+          tokenMint: mint,
+          userTokenAccount: userTokenAccount,
+          vaultTokenAccount: lendVaultTokenPda,
+          rewardReserve: lendReservePda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+      await program.methods
+        .releaseCollateral(new anchor.BN(shares))
+        .accounts({
+          borrowVault: lendVaultPda,
+          collateralVault: streamVaultPda,
+          collateralConfig: collateralConfigPda,
+          borrowPosition: borrowPositionPda,
+          userPosition: streamPositionPda,
+          user: user.publicKey,
+          systemProgram: SystemProgram.programId,
+          collateralPriceFeed: null,
+          borrowPriceFeed: null,
+        })
+        .remainingAccounts(streams)
+        .signers([user])
+        .rpc();
+
+      // Nothing was emitted while the shares were escrowed, so the escrow owes nobody more
+      const stream = await program.account.rewardStream.fetch(streamPda(0));
+      assert.equal(stream.unclaimedRewards.toNumber(), unclaimedBefore);
+      const position = await program.account.userPosition.fetch(streamPositionPda);
+      assert.equal(position.pendingRewards[0].toNumber(), pendingBefore);
+      assert.equal(position.shares.toNumber(), shares);
+    });
+
+    it("Sweeps emissions an ended stream had no shareholders to give to", async () => {
+      const emptyVaultId = new anchor.BN(5);
+      const emptyIdSeed = emptyVaultId.toArrayLike(Buffer, "le", 8);
+      const [emptyVaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), mint.toBuffer(), emptyIdSeed],
+        program.programId
+      );
+      await program.methods
+        .initializeVault(emptyVaultId, new anchor.BN(interestRate), new anchor.BN(minDeposit), { simple: {} })
+        .accounts({
+          vault: emptyVaultPda,
+          authority: authority.publicKey,
+          tokenMint: mint,
+          tokenVault: PublicKey.findProgramAddressSync(
+            [Buffer.from("vault-token"), mint.toBuffer(), emptyIdSeed],
+            program.programId
+          )[0],
+          rewardReserve: PublicKey.findProgramAddressSync(
+            [Buffer.from("vault-reserve"), mint.toBuffer(), emptyIdSeed],
+            program.programId
+          )[0],
+          treasury: treasuryTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .rpc();
+
+      await addStream(0, emptyVaultPda, 3);
+      const rewardIndex = rewardMints.length - 1;
+      const funded = await balanceOf(escrowPda(0, emptyVaultPda));
+      const sweep = () =>
+        program.methods
+          .sweepRewardStream()
+          .accounts({
+            vault: emptyVaultPda,
+            rewardStream: streamPda(0, emptyVaultPda),
+            rewardMint: rewardMints[rewardIndex],
+            rewardEscrow: escrowPda(0, emptyVaultPda),
+            authorityRewardAccount: authorityRewardAccounts[rewardIndex],
+            authority: authority.publicKey,
+            rewardTokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+
+      try {
+        await sweep();
+        assert.fail("Should have failed with the stream still running");
+      } catch (error) {
+        assert.include((error as Error).toString(), "RewardStreamActive");
+      }
+
+      await new Promise((resolve) => setTimeout(resolve, 5000));
+      const balanceBefore = await balanceOf(authorityRewardAccounts[rewardIndex]);
+      await sweep();
+
+      // Nobody held shares, so the whole escrow comes back
+      assert.equal(await balanceOf(authorityRewardAccounts[rewardIndex]) - balanceBefore, funded);
+      assert.equal(await balanceOf(escrowPda(0, emptyVaultPda)), 0);
+
+      // Nothing is left to claim, so the stream can be retired and its slot reused
+      await program.methods
+        .closeRewardStream()
+        .accounts({
+          vault: emptyVaultPda,
+          rewardStream: streamPda(0, emptyVaultPda),
+          rewardMint: rewardMints[rewardIndex],
+          rewardEscrow: escrowPda(0, emptyVaultPda),
+          authorityRewardAccount: authorityRewardAccounts[rewardIndex],
+          authority: authority.publicKey,
+          rewardTokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      let vault = await program.account.vault.fetch(emptyVaultPda);
+      assert.isNull(vault.rewardStreams[0]);
+      assert.isNull(await provider.connection.getAccountInfo(escrowPda(0, emptyVaultPda)));
+
+      await addStream(0, emptyVaultPda, 3);
+      vault = await program.account.vault.fetch(emptyVaultPda);
+      assert.isTrue(vault.rewardStreams[0].equals(streamPda(0, emptyVaultPda)));
+
+      // A sunset vault only closes once its streams are retired, since claims need the vault
+      const emptyVaultTokenPda = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault-token"), mint.toBuffer(), emptyIdSeed],
+        program.programId
+      )[0];
+      const emptyReservePda = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault-reserve"), mint.toBuffer(), emptyIdSeed],
+        program.programId
+      )[0];
+      const closeVault = () =>
+        program.methods
+          .closeVault()
+          .accounts({
+            vault: emptyVaultPda,
+            authority: authority.publicKey,
+            tokenMint: mint,
+            authorityTokenAccount: authorityTokenAccount,
+            vaultTokenAccount: emptyVaultTokenPda,
+            rewardReserve: emptyReservePda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+      await program.methods
+        .beginSunset(new anchor.BN(0))
+        .accounts({ vault: emptyVaultPda, authority: authority.publicKey })
+        .rpc();
+      try {
+        await closeVault();
+        assert.fail("Should have failed with reward streams open");
+      } catch (error) {
+        assert.include((error as Error).toString(), "RewardStreamsOpen");
+      }
+
+      // Past the deadline a stream may be retired before it ends
+      const replacementIndex = rewardMints.length - 1;
+      await program.methods
+        .closeRewardStream()
+        .accounts({
+          vault: emptyVaultPda,
+          rewardStream: streamPda(0, emptyVaultPda),
+          rewardMint: rewardMints[replacementIndex],
+          rewardEscrow: escrowPda(0, emptyVaultPda),
+          authorityRewardAccount: authorityRewardAccounts[replacementIndex],
+          authority: authority.publicKey,
+          rewardTokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      await closeVault();
+      assert.isNull(await provider.connection.getAccountInfo(emptyVaultPda));
+    });
+  });
+
+  it("Lends within a transaction against a later flash_repay", async () => {
+    const fees = {
+      entryFeeBps: 0,
+      exitFeeBps: 0,
+      performanceFeeBps: 0,
+      managementFeeBps: 0,
+      flashLoanFeeBps: 100,
+    };
+    await program.methods
+      .setFees(fees)
+      .accounts({ vault: vaultPda, authority: authority.publicKey })
+      .rpc();
+
+    const amount = minDeposit;
+    const flashBorrow = program.methods
+      .flashBorrow(new anchor.BN(amount))
+      .accounts({
+        vault: vaultPda,
+        borrower: user.publicKey,
+        tokenMint: mint,
+        borrowerTokenAccount: userTokenAccount,
+        vaultTokenAccount: vaultTokenPda,
+        instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user]);
+
+    try {
+      await flashBorrow.rpc();
+      assert.fail("Should have failed without a flash_repay");
+    } catch (error) {
+      assert.include((error as Error).toString(), "InvalidFlashLoan");
+    }
+
+    const flashRepay = await program.methods
+      .flashRepay()
+      .accounts({
+        vault: vaultPda,
+        payer: user.publicKey,
+        tokenMint: mint,
+        payerTokenAccount: userTokenAccount,
+        vaultTokenAccount: vaultTokenPda,
+        rewardReserve: rewardReservePda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+
+    const vaultBefore = await program.account.vault.fetch(vaultPda);
+    const balanceBefore = (await getAccount(provider.connection, vaultTokenPda)).amount;
+    await flashBorrow.postInstructions([flashRepay]).rpc();
+
+    // Principal is back and the 1% fee is credited to depositors as interest
+    const vault = await program.account.vault.fetch(vaultPda);
+    assert.equal((await getAccount(provider.connection, vaultTokenPda)).amount, balanceBefore);
+    assert.equal(vault.flashLoanAmount.toNumber(), 0);
+    assert.isAtLeast(
+      vault.totalAccruedInterest.toNumber() - vaultBefore.totalAccruedInterest.toNumber(),
+      amount / 100
+    );
+
+    await program.methods
+      .setFees({ ...fees, flashLoanFeeBps: 0 })
+      .accounts({ vault: vaultPda, authority: authority.publicKey })
+      .rpc();
+  });
+
+  it("Deploys idle funds to a strategy and harvests its yield", async () => {
+    const mockStrategy = anchor.workspace.MockStrategy as Program<MockStrategy>;
+    const [strategyPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("strategy"), vaultPda.toBuffer(), mockStrategy.programId.toBuffer()],
+      program.programId
+    );
+    const [statePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("state"), strategyPda.toBuffer()],
+      mockStrategy.programId
+    );
+    const [fundsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("funds"), statePda.toBuffer()],
+      mockStrategy.programId
+    );
+
+    await mockStrategy.methods
+      .initialize()
+      .accounts({
+        strategy: strategyPda,
+        state: statePda,
+        tokenMint: mint,
+        funds: fundsPda,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    await program.methods
+      .addStrategy(new anchor.BN(minDeposit))
+      .accounts({
+        vault: vaultPda,
+        strategy: strategyPda,
+        strategyProgram: mockStrategy.programId,
+        depositAccount: fundsPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const strategyAccounts = {
+      vault: vaultPda,
+      strategy: strategyPda,
+      strategyProgram: mockStrategy.programId,
+      depositAccount: fundsPda,
+      authority: authority.publicKey,
+      tokenMint: mint,
+      vaultTokenAccount: vaultTokenPda,
+      rewardReserve: rewardReservePda,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+    const mockAccounts = [
+      { pubkey: statePda, isWritable: true, isSigner: false },
+      { pubkey: fundsPda, isWritable: true, isSigner: false },
+    ];
+
+    try {
+      await program.methods
+        .allocate(new anchor.BN(2 * minDeposit))
+        .accounts(strategyAccounts)
+        .remainingAccounts(mockAccounts)
+        .rpc();
+      assert.fail("Should have failed with the allocation cap exceeded");
+    } catch (error) {
+      assert.include((error as Error).toString(), "AllocationCapExceeded");
+    }
+
+    await program.methods
+      .allocate(new anchor.BN(minDeposit))
+      .accounts(strategyAccounts)
+      .remainingAccounts(mockAccounts)
+      .rpc();
+
+    let vault = await program.account.vault.fetch(vaultPda);
+    assert.equal(vault.totalAllocated.toNumber(), minDeposit);
+    assert.equal(Number((await getAccount(provider.connection, fundsPda)).amount), minDeposit);
+
+    // Simulate the strategy earning yield
+    const gain = minDeposit / 10;
+    await mintTo(provider.connection, authority.payer, mint, fundsPda, authority.payer, gain);
+    const reserveBefore = Number((await getAccount(provider.connection, rewardReservePda)).amount);
+    const interestBefore = (await program.account.vault.fetch(vaultPda)).totalAccruedInterest.toNumber();
+
+    await program.methods
+      .harvest()
+      .accounts(strategyAccounts)
+      .remainingAccounts(mockAccounts)
+      .rpc();
+
+    const reserveAfter = Number((await getAccount(provider.connection, rewardReservePda)).amount);
+    assert.equal(reserveAfter - reserveBefore, gain);
+    const strategy = await program.account.strategy.fetch(strategyPda);
+    assert.equal(strategy.totalHarvested.toNumber(), gain);
+    // Harvested yield is credited to depositors as interest
+    vault = await program.account.vault.fetch(vaultPda);
+    assert.isAtLeast(vault.totalAccruedInterest.toNumber() - interestBefore, gain);
+
+    await program.methods
+      .deallocate(new anchor.BN(minDeposit))
+      .accounts(strategyAccounts)
+      .remainingAccounts(mockAccounts)
+      .rpc();
+
+    vault = await program.account.vault.fetch(vaultPda);
+    assert.equal(vault.totalAllocated.toNumber(), 0);
+  });
+
+  it("Socializes reported strategy losses and goes withdraw-only past the threshold", async () => {
+    const PAUSE_DEPOSITS = 1;
+    const mockStrategy = anchor.workspace.MockStrategy as Program<MockStrategy>;
+    const [strategyPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("strategy"), vaultPda.toBuffer(), mockStrategy.programId.toBuffer()],
+      program.programId
+    );
+    const [statePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("state"), strategyPda.toBuffer()],
+      mockStrategy.programId
+    );
+    const [fundsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("funds"), statePda.toBuffer()],
+      mockStrategy.programId
+    );
+    const strategyAccounts = {
+      vault: vaultPda,
+      strategy: strategyPda,
+      strategyProgram: mockStrategy.programId,
+      depositAccount: fundsPda,
+      authority: authority.publicKey,
+      tokenMint: mint,
+      vaultTokenAccount: vaultTokenPda,
+      rewardReserve: rewardReservePda,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+    const mockAccounts = [
+      { pubkey: statePda, isWritable: true, isSigner: false },
+      { pubkey: fundsPda, isWritable: true, isSigner: false },
+    ];
+    const reportAccounts = { vault: vaultPda, strategy: strategyPda, authority: authority.publicKey };
+
+    await program.methods
+      .setLossThreshold(1)
+      .accounts({ vault: vaultPda, authority: authority.publicKey })
+      .rpc();
+    await program.methods
+      .allocate(new anchor.BN(minDeposit))
+      .accounts(strategyAccounts)
+      .remainingAccounts(mockAccounts)
+      .rpc();
+
+    const loss = minDeposit / 2;
+    let before = await program.account.vault.fetch(vaultPda);
+    await program.methods
+      .report(new anchor.BN(0), new anchor.BN(loss))
+      .accounts(reportAccounts)
+      .rpc();
+
+    let vault = await program.account.vault.fetch(vaultPda);
+    assert.equal(vault.totalDeposited.toNumber(), before.totalDeposited.toNumber() - loss);
+    assert.equal(vault.totalShares.toNumber(), before.totalShares.toNumber());
+    assert.equal(vault.totalAllocated.toNumber(), minDeposit - loss);
+    assert.equal(vault.pausedFlags & PAUSE_DEPOSITS, PAUSE_DEPOSITS);
+
+    try {
+      await program.methods
+        .deposit(new anchor.BN(minDeposit))
+        .accounts({
+          vault: vaultPda,
+          userPosition: userPositionPda,
+          user: user.publicKey,
+          tokenMint: mint,
+          userTokenAccount: userTokenAccount,
+          vaultTokenAccount: vaultTokenPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([user])
+        .rpc();
+      assert.fail("Should have failed with paused");
+    } catch (error) {
+      assert.include((error as Error).toString(), "Paused");
+    }
+
+    // The strategy recovers, so the written-off principal comes back as a gain
+    before = vault;
+    await program.methods
+      .report(new anchor.BN(loss), new anchor.BN(0))
+      .accounts(reportAccounts)
+      .rpc();
+
+    vault = await program.account.vault.fetch(vaultPda);
+    assert.equal(vault.totalDeposited.toNumber(), before.totalDeposited.toNumber() + loss);
+    const strategy = await program.account.strategy.fetch(strategyPda);
+    assert.equal(strategy.allocated.toNumber(), minDeposit);
+
+    await program.methods
+      .deallocate(new anchor.BN(minDeposit))
+      .accounts(strategyAccounts)
+      .remainingAccounts(mockAccounts)
+      .rpc();
+    await program.methods
+      .setPause(0)
+      .accounts({ vault: vaultPda, authority: authority.publicKey })
+      .rpc();
+    await program.methods
+      .setLossThreshold(null)
+      .accounts({ vault: vaultPda, authority: authority.publicKey })
+      .rpc();
+  });
+
+  it("Refuses deposits once reported losses wipe out every asset", async () => {
+    const mockStrategy = anchor.workspace.MockStrategy as Program<MockStrategy>;
+    const vaultId = new anchor.BN(6);
+    const idSeed = vaultId.toArrayLike(Buffer, "le", 8);
+    const [lossVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), mint.toBuffer(), idSeed],
+      program.programId
+    );
+    const [lossVaultTokenPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault-token"), mint.toBuffer(), idSeed],
+      program.programId
+    );
+    const [lossReservePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault-reserve"), mint.toBuffer(), idSeed],
+      program.programId
+    );
+    const [lossPositionPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user-position"), lossVaultPda.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+    const [strategyPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("strategy"), lossVaultPda.toBuffer(), mockStrategy.programId.toBuffer()],
+      program.programId
+    );
+    const [statePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("state"), strategyPda.toBuffer()],
+      mockStrategy.programId
+    );
+    const [fundsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("funds"), statePda.toBuffer()],
+      mockStrategy.programId
+    );
+    const depositAccounts = {
+      vault: lossVaultPda,
+      userPosition: lossPositionPda,
+      user: user.publicKey,
+      tokenMint: mint,
+      userTokenAccount: userTokenAccount,
+      vaultTokenAccount: lossVaultTokenPda,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+    };
+
+    // No interest, so the loss below leaves total assets at exactly zero
+    await program.methods
+      .initializeVault(vaultId, new anchor.BN(0), new anchor.BN(minDeposit), { simple: {} })
+      .accounts({
+        vault: lossVaultPda,
+        authority: authority.publicKey,
+        tokenMint: mint,
+        tokenVault: lossVaultTokenPda,
+        rewardReserve: lossReservePda,
+        treasury: treasuryTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+    await program.methods
+      .deposit(new anchor.BN(minDeposit))
+      .accounts(depositAccounts)
+      .signers([user])
+      .rpc();
+
+    await mockStrategy.methods
+      .initialize()
+      .accounts({
+        strategy: strategyPda,
+        state: statePda,
+        tokenMint: mint,
+        funds: fundsPda,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+    await program.methods
+      .addStrategy(new anchor.BN(minDeposit))
+      .accounts({
+        vault: lossVaultPda,
+        strategy: strategyPda,
+        strategyProgram: mockStrategy.programId,
+        depositAccount: fundsPda,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    await program.methods
+      .allocate(new anchor.BN(minDeposit))
+      .accounts({
+        vault: lossVaultPda,
+        strategy: strategyPda,
+        strategyProgram: mockStrategy.programId,
+        depositAccount: fundsPda,
+        authority: authority.publicKey,
+        tokenMint: mint,
+        vaultTokenAccount: lossVaultTokenPda,
+        rewardReserve: lossReservePda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        { pubkey: statePda, isWritable: true, isSigner: false },
+        { pubkey: fundsPda, isWritable: true, isSigner: false },
+      ])
+      .rpc();
+    await program.methods
+      .report(new anchor.BN(0), new anchor.BN(minDeposit))
+      .accounts({ vault: lossVaultPda, strategy: strategyPda, authority: authority.publicKey })
+      .rpc();
+
+    const vault = await program.account.vault.fetch(lossVaultPda);
+    assert.equal(vault.totalDeposited.toNumber() + vault.totalAccruedInterest.toNumber(), 0);
+    assert.equal(vault.totalShares.toNumber(), minDeposit);
+
+    try {
+      await program.methods
+        .deposit(new anchor.BN(minDeposit))
+        .accounts(depositAccounts)
+        .signers([user])
+        .rpc();
+      assert.fail("Should have failed with the vault insolvent");
+    } catch (error) {
+      assert.include((error as Error).toString(), "VaultInsolvent");
+    }
+  });
+
   it("Prevents deposits below minimum amount", async () => {
     const smallAmount = minDeposit - 1;
 
@@ -221,6 +2794,7 @@ describe("defi-vault", () => {
           vault: vaultPda,
           userPosition: userPositionPda,
           user: user.publicKey,
+          tokenMint: mint,
           userTokenAccount: userTokenAccount,
           vaultTokenAccount: vaultTokenPda,
           tokenProgram: TOKEN_PROGRAM_ID,
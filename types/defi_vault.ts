@@ -25,6 +25,16 @@ export type DefiVault = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "rewardReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "tokenProgram",
           "isMut": false,
@@ -42,6 +52,10 @@ export type DefiVault = {
         }
       ],
       "args": [
+        {
+          "name": "vaultId",
+          "type": "u64"
+        },
         {
           "name": "interestRate",
           "type": "u64"
@@ -49,6 +63,12 @@ export type DefiVault = {
         {
           "name": "minDeposit",
           "type": "u64"
+        },
+        {
+          "name": "interestModel",
+          "type": {
+            "defined": "InterestModel"
+          }
         }
       ]
     },
@@ -70,6 +90,11 @@ export type DefiVault = {
           "isMut": true,
           "isSigner": true
         },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "userTokenAccount",
           "isMut": true,
@@ -104,7 +129,7 @@ export type DefiVault = {
       ]
     },
     {
-      "name": "withdraw",
+      "name": "depositLocked",
       "accounts": [
         {
           "name": "vault",
@@ -116,11 +141,21 @@ export type DefiVault = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "lockPosition",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "user",
           "isMut": true,
           "isSigner": true
         },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "userTokenAccount",
           "isMut": true,
@@ -135,208 +170,87 @@ export type DefiVault = {
           "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
         {
           "name": "amount",
           "type": "u64"
+        },
+        {
+          "name": "termId",
+          "type": "u8"
         }
       ]
     },
     {
-      "name": "getUserBalance",
+      "name": "updateVaultParams",
       "accounts": [
         {
           "name": "vault",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "userPosition",
+          "name": "authority",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         }
       ],
-      "args": []
-    }
-  ],
-  "accounts": [
-    {
-      "name": "vault",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "authority",
-            "type": "publicKey"
-          },
-          {
-            "name": "tokenMint",
-            "type": "publicKey"
-          },
-          {
-            "name": "tokenVault",
-            "type": "publicKey"
-          },
-          {
-            "name": "interestRate",
-            "type": "u64"
-          },
-          {
-            "name": "minDeposit",
-            "type": "u64"
-          },
-          {
-            "name": "totalDeposited",
-            "type": "u64"
-          },
-          {
-            "name": "bump",
-            "type": "u8"
-          },
-          {
-            "name": "createdAt",
-            "type": "i64"
-          }
-        ]
-      }
-    },
-    {
-      "name": "userPosition",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "owner",
-            "type": "publicKey"
-          },
-          {
-            "name": "vault",
-            "type": "publicKey"
-          },
-          {
-            "name": "depositedAmount",
-            "type": "u64"
-          },
-          {
-            "name": "accruedInterest",
-            "type": "u64"
-          },
-          {
-            "name": "lastUpdateTime",
-            "type": "i64"
-          },
-          {
-            "name": "depositCount",
-            "type": "u64"
-          },
-          {
-            "name": "withdrawCount",
-            "type": "u64"
-          }
-        ]
-      }
-    }
-  ],
-  "types": [
-    {
-      "name": "UserBalanceInfo",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "depositedAmount",
-            "type": "u64"
-          },
-          {
-            "name": "accruedInterest",
-            "type": "u64"
-          },
-          {
-            "name": "totalBalance",
-            "type": "u64"
-          },
-          {
-            "name": "lastUpdateTime",
-            "type": "i64"
-          }
-        ]
-      }
-    }
-  ],
-  "events": [
-    {
-      "name": "DepositEvent",
-      "fields": [
-        {
-          "name": "user",
-          "type": "publicKey",
-          "index": false
-        },
+      "args": [
         {
-          "name": "vault",
-          "type": "publicKey",
-          "index": false
+          "name": "interestRate",
+          "type": "u64"
         },
         {
-          "name": "amount",
-          "type": "u64",
-          "index": false
+          "name": "minDeposit",
+          "type": "u64"
         },
         {
-          "name": "timestamp",
-          "type": "i64",
-          "index": false
+          "name": "interestModel",
+          "type": {
+            "defined": "InterestModel"
+          }
         }
       ]
     },
     {
-      "name": "WithdrawEvent",
-      "fields": [
-        {
-          "name": "user",
-          "type": "publicKey",
-          "index": false
-        },
+      "name": "setRateCurve",
+      "accounts": [
         {
           "name": "vault",
-          "type": "publicKey",
-          "index": false
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "amount",
-          "type": "u64",
-          "index": false
-        },
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
         {
-          "name": "timestamp",
-          "type": "i64",
-          "index": false
+          "name": "rateCurve",
+          "type": {
+            "option": {
+              "defined": "RateCurve"
+            }
+          }
         }
       ]
-    }
-  ],
-  "errors": [
-    {
-      "code": 6000,
-      "name": "InsufficientDepositAmount",
-      "msg": "Insufficient deposit amount"
     },
     {
-      "code": 6001,
-      "name": "InsufficientBalance",
-      "msg": "Insufficient balance for withdrawal"
-    }
-  ]
-};
-
-export const IDL: DefiVault = {
-  "version": "0.1.0",
-  "name": "defi_vault",
-  "instructions": [
-    {
-      "name": "initializeVault",
+      "name": "setFees",
       "accounts": [
         {
           "name": "vault",
@@ -345,48 +259,152 @@ export const IDL: DefiVault = {
         },
         {
           "name": "authority",
-          "isMut": true,
+          "isMut": false,
           "isSigner": true
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "tokenMint",
-          "isMut": false,
-          "isSigner": false
-        },
+          "name": "fees",
+          "type": {
+            "defined": "FeeConfig"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setDepositCaps",
+      "accounts": [
         {
-          "name": "tokenVault",
+          "name": "vault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "authority",
           "isMut": false,
-          "isSigner": false
-        },
+          "isSigner": true
+        }
+      ],
+      "args": [
         {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
+          "name": "maxTotalDeposits",
+          "type": {
+            "option": "u64"
+          }
         },
         {
-          "name": "rent",
+          "name": "maxPerUser",
+          "type": {
+            "option": "u64"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setWithdrawalCooldown",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "withdrawalCooldown",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "setGuardian",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "guardian",
+          "type": {
+            "option": "publicKey"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setOutflowLimit",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "signer",
           "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "outflowLimit",
+          "type": {
+            "defined": "OutflowLimit"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setLockTerm",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
           "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
         }
       ],
       "args": [
+        {
+          "name": "termId",
+          "type": "u8"
+        },
+        {
+          "name": "duration",
+          "type": "i64"
+        },
         {
           "name": "interestRate",
           "type": "u64"
         },
         {
-          "name": "minDeposit",
-          "type": "u64"
+          "name": "earlyWithdrawalPenaltyBps",
+          "type": {
+            "option": "u16"
+          }
         }
       ]
     },
     {
-      "name": "deposit",
+      "name": "setPause",
       "accounts": [
         {
           "name": "vault",
@@ -394,37 +412,101 @@ export const IDL: DefiVault = {
           "isSigner": false
         },
         {
-          "name": "userPosition",
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "pausedFlags",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "proposeAuthority",
+      "accounts": [
+        {
+          "name": "vault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "user",
-          "isMut": true,
+          "name": "authority",
+          "isMut": false,
           "isSigner": true
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "userTokenAccount",
+          "name": "newAuthority",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "acceptAuthority",
+      "accounts": [
+        {
+          "name": "vault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "vaultTokenAccount",
+          "name": "pendingAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "cancelAuthorityTransfer",
+      "accounts": [
+        {
+          "name": "vault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "fundReserve",
+      "accounts": [
+        {
+          "name": "vault",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "authorityTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         }
@@ -436,6 +518,42 @@ export const IDL: DefiVault = {
         }
       ]
     },
+    {
+      "name": "collectFees",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
     {
       "name": "withdraw",
       "accounts": [
@@ -454,6 +572,11 @@ export const IDL: DefiVault = {
           "isMut": true,
           "isSigner": true
         },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "userTokenAccount",
           "isMut": true,
@@ -464,6 +587,11 @@ export const IDL: DefiVault = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "rewardReserve",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "tokenProgram",
           "isMut": false,
@@ -478,143 +606,8151 @@ export const IDL: DefiVault = {
       ]
     },
     {
-      "name": "getUserBalance",
+      "name": "requestWithdraw",
       "accounts": [
         {
           "name": "vault",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
         {
           "name": "userPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pendingWithdrawal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": []
-    }
-  ],
-  "accounts": [
-    {
-      "name": "vault",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "authority",
-            "type": "publicKey"
-          },
-          {
-            "name": "tokenMint",
-            "type": "publicKey"
-          },
-          {
-            "name": "tokenVault",
-            "type": "publicKey"
-          },
-          {
-            "name": "interestRate",
-            "type": "u64"
-          },
-          {
-            "name": "minDeposit",
-            "type": "u64"
-          },
-          {
-            "name": "totalDeposited",
-            "type": "u64"
-          },
-          {
-            "name": "bump",
-            "type": "u8"
-          },
-          {
-            "name": "createdAt",
-            "type": "i64"
-          }
-        ]
-      }
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "name": "userPosition",
+      "name": "claimWithdraw",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pendingWithdrawal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "withdrawLocked",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lockPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closePosition",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": true,
+          "isSigner": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "borrow",
+      "accounts": [
+        {
+          "name": "borrowVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collateralVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collateralConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "borrowPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateralPriceFeed",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "borrowPriceFeed",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        }
+      ],
+      "args": [
+        {
+          "name": "collateralShares",
+          "type": "u64"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "repay",
+      "accounts": [
+        {
+          "name": "borrowVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "borrowPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "releaseCollateral",
+      "accounts": [
+        {
+          "name": "borrowVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collateralVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collateralConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "borrowPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateralPriceFeed",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "borrowPriceFeed",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        }
+      ],
+      "args": [
+        {
+          "name": "shares",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "liquidate",
+      "accounts": [
+        {
+          "name": "borrowVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collateralVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collateralConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "borrowPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "borrower",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liquidatorPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liquidator",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liquidatorTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateralPriceFeed",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "borrowPriceFeed",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        }
+      ],
+      "args": [
+        {
+          "name": "repayAmount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setBorrowConfig",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "borrowConfig",
+          "type": {
+            "defined": "BorrowConfig"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setCollateralConfig",
+      "accounts": [
+        {
+          "name": "borrowVault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateralVault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateralConfig",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "priceFeed",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "enabled",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setPriceFeed",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "priceFeed",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "initializePriceFeed",
+      "accounts": [
+        {
+          "name": "priceFeed",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "price",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "updatePrice",
+      "accounts": [
+        {
+          "name": "priceFeed",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "price",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "addRewardStream",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardStream",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rewardEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authorityRewardAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rewardTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "index",
+          "type": "u8"
+        },
+        {
+          "name": "emissionRate",
+          "type": "u64"
+        },
+        {
+          "name": "startTime",
+          "type": "i64"
+        },
+        {
+          "name": "endTime",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "extendRewardStream",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rewardStream",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rewardEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authorityRewardAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "rewardTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "newEndTime",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "sweepRewardStream",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rewardStream",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rewardEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authorityRewardAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "rewardTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closeRewardStream",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardStream",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rewardEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authorityRewardAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rewardTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimRewards",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardStream",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rewardEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userRewardAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "rewardTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimAllRewards",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "flashBorrow",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "borrower",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "borrowerTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "flashRepay",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payerTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "addStrategy",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "strategy",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "strategyProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "depositAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "allocationCap",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setAllocationCap",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "strategy",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "allocationCap",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setLossThreshold",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "lossThresholdBps",
+          "type": {
+            "option": "u16"
+          }
+        }
+      ]
+    },
+    {
+      "name": "allocate",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "strategy",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "strategyProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "depositAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "deallocate",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "strategy",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "strategyProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "depositAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "harvest",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "strategy",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "strategyProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "depositAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "report",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "strategy",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "gain",
+          "type": "u64"
+        },
+        {
+          "name": "loss",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "beginSunset",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "gracePeriod",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "closeVault",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authorityTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "getUserBalance",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userPosition",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [],
+      "returns": {
+        "defined": "UserBalanceInfo"
+      }
+    },
+    {
+      "name": "previewDeposit",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ],
+      "returns": "u64"
+    },
+    {
+      "name": "previewWithdraw",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ],
+      "returns": "u64"
+    },
+    {
+      "name": "convertToAssets",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "shares",
+          "type": "u64"
+        }
+      ],
+      "returns": "u64"
+    }
+  ],
+  "accounts": [
+    {
+      "name": "vault",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "pendingAuthority",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "vaultId",
+            "type": "u64"
+          },
+          {
+            "name": "tokenVault",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenProgram",
+            "type": "publicKey"
+          },
+          {
+            "name": "rewardReserve",
+            "type": "publicKey"
+          },
+          {
+            "name": "treasury",
+            "type": "publicKey"
+          },
+          {
+            "name": "interestRate",
+            "type": "u64"
+          },
+          {
+            "name": "interestModel",
+            "type": {
+              "defined": "InterestModel"
+            }
+          },
+          {
+            "name": "rateCurve",
+            "type": {
+              "option": {
+                "defined": "RateCurve"
+              }
+            }
+          },
+          {
+            "name": "totalBorrowed",
+            "type": "u64"
+          },
+          {
+            "name": "totalCollateralShares",
+            "type": "u64"
+          },
+          {
+            "name": "borrowRate",
+            "type": "u64"
+          },
+          {
+            "name": "borrowIndex",
+            "type": "u128"
+          },
+          {
+            "name": "borrowIndexUpdatedAt",
+            "type": "i64"
+          },
+          {
+            "name": "borrowConfig",
+            "type": {
+              "defined": "BorrowConfig"
+            }
+          },
+          {
+            "name": "priceFeed",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "rewardStreams",
+            "type": {
+              "array": [
+                {
+                  "option": "publicKey"
+                },
+                4
+              ]
+            }
+          },
+          {
+            "name": "flashLoanAmount",
+            "type": "u64"
+          },
+          {
+            "name": "flashLoanFee",
+            "type": "u64"
+          },
+          {
+            "name": "totalAllocated",
+            "type": "u64"
+          },
+          {
+            "name": "lossThresholdBps",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "minDeposit",
+            "type": "u64"
+          },
+          {
+            "name": "fees",
+            "type": {
+              "defined": "FeeConfig"
+            }
+          },
+          {
+            "name": "maxTotalDeposits",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "maxPerUser",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "withdrawalCooldown",
+            "type": "i64"
+          },
+          {
+            "name": "guardian",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "outflowLimit",
+            "type": {
+              "defined": "OutflowLimit"
+            }
+          },
+          {
+            "name": "outflowWindowStart",
+            "type": "i64"
+          },
+          {
+            "name": "outflowWindowAmount",
+            "type": "u64"
+          },
+          {
+            "name": "outflowWindowTvl",
+            "type": "u64"
+          },
+          {
+            "name": "pausedFlags",
+            "type": "u8"
+          },
+          {
+            "name": "totalDeposited",
+            "type": "u64"
+          },
+          {
+            "name": "totalShares",
+            "type": "u64"
+          },
+          {
+            "name": "totalAccruedInterest",
+            "type": "u64"
+          },
+          {
+            "name": "lastAccrualTime",
+            "type": "i64"
+          },
+          {
+            "name": "totalLocked",
+            "type": "u64"
+          },
+          {
+            "name": "totalLockedInterest",
+            "type": "u64"
+          },
+          {
+            "name": "accruedVaultFees",
+            "type": "u64"
+          },
+          {
+            "name": "accruedReserveFees",
+            "type": "u64"
+          },
+          {
+            "name": "pendingPrincipal",
+            "type": "u64"
+          },
+          {
+            "name": "pendingInterest",
+            "type": "u64"
+          },
+          {
+            "name": "lockTerms",
+            "type": {
+              "array": [
+                {
+                  "defined": "LockTerm"
+                },
+                4
+              ]
+            }
+          },
+          {
+            "name": "latestUnlockTime",
+            "type": "i64"
+          },
+          {
+            "name": "sunsetDeadline",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "createdAt",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "userPosition",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "type": "publicKey"
+          },
+          {
+            "name": "vault",
+            "type": "publicKey"
+          },
+          {
+            "name": "shares",
+            "type": "u64"
+          },
+          {
+            "name": "depositedAmount",
+            "type": "u64"
+          },
+          {
+            "name": "lastUpdateTime",
+            "type": "i64"
+          },
+          {
+            "name": "depositCount",
+            "type": "u64"
+          },
+          {
+            "name": "withdrawCount",
+            "type": "u64"
+          },
+          {
+            "name": "lockCount",
+            "type": "u64"
+          },
+          {
+            "name": "openLocks",
+            "type": "u64"
+          },
+          {
+            "name": "lockedAmount",
+            "type": "u64"
+          },
+          {
+            "name": "rewardPerSharePaid",
+            "type": {
+              "array": [
+                "u128",
+                4
+              ]
+            }
+          },
+          {
+            "name": "pendingRewards",
+            "type": {
+              "array": [
+                "u64",
+                4
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "rewardStream",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "vault",
+            "type": "publicKey"
+          },
+          {
+            "name": "index",
+            "type": "u8"
+          },
+          {
+            "name": "rewardMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "rewardEscrow",
+            "type": "publicKey"
+          },
+          {
+            "name": "rewardTokenProgram",
+            "type": "publicKey"
+          },
+          {
+            "name": "emissionRate",
+            "type": "u64"
+          },
+          {
+            "name": "startTime",
+            "type": "i64"
+          },
+          {
+            "name": "endTime",
+            "type": "i64"
+          },
+          {
+            "name": "rewardPerShare",
+            "type": "u128"
+          },
+          {
+            "name": "unclaimedRewards",
+            "type": "u64"
+          },
+          {
+            "name": "lastUpdateTime",
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "strategy",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "vault",
+            "type": "publicKey"
+          },
+          {
+            "name": "strategyProgram",
+            "type": "publicKey"
+          },
+          {
+            "name": "depositAccount",
+            "type": "publicKey"
+          },
+          {
+            "name": "allocationCap",
+            "type": "u64"
+          },
+          {
+            "name": "allocated",
+            "type": "u64"
+          },
+          {
+            "name": "totalHarvested",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "lockPosition",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "type": "publicKey"
+          },
+          {
+            "name": "vault",
+            "type": "publicKey"
+          },
+          {
+            "name": "lockId",
+            "type": "u64"
+          },
+          {
+            "name": "termId",
+            "type": "u8"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "interestRate",
+            "type": "u64"
+          },
+          {
+            "name": "earlyWithdrawalPenaltyBps",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "startTime",
+            "type": "i64"
+          },
+          {
+            "name": "unlockTime",
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "borrowPosition",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "type": "publicKey"
+          },
+          {
+            "name": "borrowVault",
+            "type": "publicKey"
+          },
+          {
+            "name": "collateralVault",
+            "type": "publicKey"
+          },
+          {
+            "name": "collateralShares",
+            "type": "u64"
+          },
+          {
+            "name": "collateralBasis",
+            "type": "u64"
+          },
+          {
+            "name": "debt",
+            "type": "u64"
+          },
+          {
+            "name": "principal",
+            "type": "u64"
+          },
+          {
+            "name": "borrowIndex",
+            "type": "u128"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "collateralConfig",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "borrowVault",
+            "type": "publicKey"
+          },
+          {
+            "name": "collateralVault",
+            "type": "publicKey"
+          },
+          {
+            "name": "priceFeed",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "enabled",
+            "type": "bool"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "priceFeed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "decimals",
+            "type": "u8"
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "updatedAt",
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "pendingWithdrawal",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "type": "publicKey"
+          },
+          {
+            "name": "vault",
+            "type": "publicKey"
+          },
+          {
+            "name": "principalAmount",
+            "type": "u64"
+          },
+          {
+            "name": "interestAmount",
+            "type": "u64"
+          },
+          {
+            "name": "claimableAt",
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    }
+  ],
+  "types": [
+    {
+      "name": "FeeConfig",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "entryFeeBps",
+            "type": "u16"
+          },
+          {
+            "name": "exitFeeBps",
+            "type": "u16"
+          },
+          {
+            "name": "performanceFeeBps",
+            "type": "u16"
+          },
+          {
+            "name": "managementFeeBps",
+            "type": "u16"
+          },
+          {
+            "name": "flashLoanFeeBps",
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "RateCurve",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "baseRateBps",
+            "type": "u64"
+          },
+          {
+            "name": "slope1Bps",
+            "type": "u64"
+          },
+          {
+            "name": "optimalUtilizationBps",
+            "type": "u64"
+          },
+          {
+            "name": "slope2Bps",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "BorrowConfig",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "maxLtvBps",
+            "type": "u16"
+          },
+          {
+            "name": "liquidationThresholdBps",
+            "type": "u16"
+          },
+          {
+            "name": "liquidationBonusBps",
+            "type": "u16"
+          },
+          {
+            "name": "closeFactorBps",
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "OutflowLimit",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "window",
+            "type": "i64"
+          },
+          {
+            "name": "maxAmount",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "maxTvlBps",
+            "type": {
+              "option": "u16"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "LockTerm",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "duration",
+            "type": "i64"
+          },
+          {
+            "name": "interestRate",
+            "type": "u64"
+          },
+          {
+            "name": "earlyWithdrawalPenaltyBps",
+            "type": {
+              "option": "u16"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "UserBalanceInfo",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "depositedAmount",
+            "type": "u64"
+          },
+          {
+            "name": "accruedInterest",
+            "type": "u64"
+          },
+          {
+            "name": "totalBalance",
+            "type": "u64"
+          },
+          {
+            "name": "shares",
+            "type": "u64"
+          },
+          {
+            "name": "lastUpdateTime",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "FeeKind",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Entry"
+          },
+          {
+            "name": "Exit"
+          },
+          {
+            "name": "Performance"
+          },
+          {
+            "name": "Management"
+          },
+          {
+            "name": "FlashLoan"
+          }
+        ]
+      }
+    },
+    {
+      "name": "InterestModel",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Simple"
+          },
+          {
+            "name": "Compound",
+            "fields": [
+              {
+                "name": "period",
+                "type": "i64"
+              }
+            ]
+          },
+          {
+            "name": "Continuous"
+          }
+        ]
+      }
+    }
+  ],
+  "events": [
+    {
+      "name": "DepositEvent",
+      "fields": [
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "netAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "feeAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "shares",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "WithdrawEvent",
+      "fields": [
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "netAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "feeAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "shares",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "interestAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "principalAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PositionClosedEvent",
+      "fields": [
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "dustShares",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "WithdrawRequestedEvent",
+      "fields": [
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "feeAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "shares",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "interestAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "principalAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "claimableAt",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "WithdrawClaimedEvent",
+      "fields": [
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "interestAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "principalAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "netAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "WithdrawalCooldownUpdatedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "oldWithdrawalCooldown",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "newWithdrawalCooldown",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "LockedDepositEvent",
+      "fields": [
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "lockId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "termId",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "netAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "interestRate",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "unlockTime",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "LockedWithdrawEvent",
+      "fields": [
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "lockId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "principalAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "interestAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "penaltyAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "netAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "early",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "DepositCapsUpdatedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "maxTotalDeposits",
+          "type": {
+            "option": "u64"
+          },
+          "index": false
+        },
+        {
+          "name": "maxPerUser",
+          "type": {
+            "option": "u64"
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "LockTermUpdatedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "termId",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "duration",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "interestRate",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "earlyWithdrawalPenaltyBps",
+          "type": {
+            "option": "u16"
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "VaultParamsUpdatedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "oldInterestRate",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "newInterestRate",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "oldMinDeposit",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "newMinDeposit",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "oldInterestModel",
+          "type": {
+            "defined": "InterestModel"
+          },
+          "index": false
+        },
+        {
+          "name": "newInterestModel",
+          "type": {
+            "defined": "InterestModel"
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RateCurveUpdatedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "rateCurve",
+          "type": {
+            "option": {
+              "defined": "RateCurve"
+            }
+          },
+          "index": false
+        },
+        {
+          "name": "interestRate",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "FeesUpdatedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "oldFees",
+          "type": {
+            "defined": "FeeConfig"
+          },
+          "index": false
+        },
+        {
+          "name": "newFees",
+          "type": {
+            "defined": "FeeConfig"
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "FeeChargedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "user",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "kind",
+          "type": {
+            "defined": "FeeKind"
+          },
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "FeesCollectedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "treasury",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "outstanding",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "SunsetStartedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "deadline",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "VaultClosedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "sweptAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "GuardianUpdatedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "oldGuardian",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "newGuardian",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "OutflowLimitUpdatedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "updatedBy",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "oldOutflowLimit",
+          "type": {
+            "defined": "OutflowLimit"
+          },
+          "index": false
+        },
+        {
+          "name": "newOutflowLimit",
+          "type": {
+            "defined": "OutflowLimit"
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "BorrowEvent",
+      "fields": [
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "borrowVault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "collateralVault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "collateralShares",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "debt",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RepayEvent",
+      "fields": [
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "borrowVault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "interestAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "principalAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "debt",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "CollateralReleasedEvent",
+      "fields": [
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "borrowVault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "collateralVault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "shares",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "debt",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "BorrowConfigUpdatedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "borrowConfig",
+          "type": {
+            "defined": "BorrowConfig"
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "CollateralConfigUpdatedEvent",
+      "fields": [
+        {
+          "name": "borrowVault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "collateralVault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "priceFeed",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "enabled",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RewardStreamAddedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "rewardStream",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "index",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "rewardMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "emissionRate",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "startTime",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "endTime",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RewardStreamSweptEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "rewardStream",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RewardStreamClosedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "rewardStream",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "sweptAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RewardStreamExtendedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "rewardStream",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "oldEndTime",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "newEndTime",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RewardsClaimedEvent",
+      "fields": [
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "rewardStream",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "StrategyAddedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "strategy",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "strategyProgram",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "depositAccount",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "allocationCap",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "AllocationCapUpdatedEvent",
+      "fields": [
+        {
+          "name": "strategy",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "oldCap",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "newCap",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "AllocationEvent",
+      "fields": [
+        {
+          "name": "strategy",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "allocated",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "DeallocationEvent",
+      "fields": [
+        {
+          "name": "strategy",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "gain",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "allocated",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "HarvestEvent",
+      "fields": [
+        {
+          "name": "strategy",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "totalHarvested",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "LossThresholdUpdatedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "oldThresholdBps",
+          "type": {
+            "option": "u16"
+          },
+          "index": false
+        },
+        {
+          "name": "newThresholdBps",
+          "type": {
+            "option": "u16"
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "StrategyReportEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "strategy",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "gain",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "loss",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "allocated",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "totalAssets",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "withdrawOnly",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "FlashBorrowEvent",
+      "fields": [
+        {
+          "name": "borrower",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "fee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "FlashRepayEvent",
+      "fields": [
+        {
+          "name": "payer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "fee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "treasuryFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "LiquidationEvent",
+      "fields": [
+        {
+          "name": "liquidator",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "borrower",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "borrowVault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "collateralVault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "interestAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "principalAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "seizedShares",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "healthFactor",
+          "type": "u128",
+          "index": false
+        },
+        {
+          "name": "debt",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PriceFeedUpdatedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "priceFeed",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PriceUpdatedEvent",
+      "fields": [
+        {
+          "name": "priceFeed",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PauseUpdatedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "pausedFlags",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "AuthorityProposedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "pendingAuthority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "AuthorityAcceptedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "previousAuthority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "newAuthority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "AuthorityTransferCancelledEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "cancelledAuthority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ReserveFundedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "funder",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "reserveBalance",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "InsufficientDepositAmount",
+      "msg": "Insufficient deposit amount"
+    },
+    {
+      "code": 6001,
+      "name": "InsufficientBalance",
+      "msg": "Insufficient balance for withdrawal"
+    },
+    {
+      "code": 6002,
+      "name": "InvalidAmount",
+      "msg": "Amount must be greater than zero"
+    },
+    {
+      "code": 6003,
+      "name": "InsufficientReserve",
+      "msg": "Reward reserve cannot cover the accrued interest"
+    },
+    {
+      "code": 6004,
+      "name": "ZeroShares",
+      "msg": "Deposit is too small to mint any shares"
+    },
+    {
+      "code": 6005,
+      "name": "Unauthorized",
+      "msg": "Signer is not the vault authority"
+    },
+    {
+      "code": 6006,
+      "name": "InvalidAuthority",
+      "msg": "New authority must differ from the current authority"
+    },
+    {
+      "code": 6007,
+      "name": "NotPendingAuthority",
+      "msg": "Signer is not the pending vault authority"
+    },
+    {
+      "code": 6008,
+      "name": "NoPendingAuthority",
+      "msg": "No authority transfer is pending"
+    },
+    {
+      "code": 6009,
+      "name": "Paused",
+      "msg": "This vault operation is paused"
+    },
+    {
+      "code": 6010,
+      "name": "InvalidPauseFlags",
+      "msg": "Unknown pause flags"
+    },
+    {
+      "code": 6011,
+      "name": "MathOverflow",
+      "msg": "Math overflow"
+    },
+    {
+      "code": 6012,
+      "name": "InvalidTokenProgram",
+      "msg": "Token program does not match the vault's token program"
+    },
+    {
+      "code": 6013,
+      "name": "InvalidInterestModel",
+      "msg": "Compounding period must be greater than zero"
+    },
+    {
+      "code": 6014,
+      "name": "InvalidLockTerm",
+      "msg": "Lock term is not configured or its parameters are invalid"
+    },
+    {
+      "code": 6015,
+      "name": "LockNotMatured",
+      "msg": "Locked deposit has not reached its unlock time"
+    },
+    {
+      "code": 6016,
+      "name": "InvalidTreasury",
+      "msg": "Treasury account does not match the vault"
+    },
+    {
+      "code": 6017,
+      "name": "InvalidFee",
+      "msg": "Fee cannot exceed 10000 basis points"
+    },
+    {
+      "code": 6018,
+      "name": "PositionNotEmpty",
+      "msg": "Position still holds shares or open locks"
+    },
+    {
+      "code": 6019,
+      "name": "InvalidPosition",
+      "msg": "Position does not belong to this user and vault"
+    },
+    {
+      "code": 6020,
+      "name": "VaultSunsetting",
+      "msg": "Vault is being sunset"
+    },
+    {
+      "code": 6021,
+      "name": "VaultNotSunsetting",
+      "msg": "Vault has not begun its sunset"
+    },
+    {
+      "code": 6022,
+      "name": "InvalidSunsetDeadline",
+      "msg": "Sunset deadline must not be before any open lock matures"
+    },
+    {
+      "code": 6023,
+      "name": "SunsetInProgress",
+      "msg": "Depositors remain and the sunset deadline has not passed"
+    },
+    {
+      "code": 6024,
+      "name": "DepositCapExceeded",
+      "msg": "Deposit would exceed the vault's total deposit cap"
+    },
+    {
+      "code": 6025,
+      "name": "UserDepositCapExceeded",
+      "msg": "Deposit would exceed the per-user deposit cap"
+    },
+    {
+      "code": 6026,
+      "name": "WithdrawalCooldownActive",
+      "msg": "Withdrawals on this vault go through request_withdraw"
+    },
+    {
+      "code": 6027,
+      "name": "WithdrawalCooldownDisabled",
+      "msg": "Vault has no withdrawal cooldown; use withdraw"
+    },
+    {
+      "code": 6028,
+      "name": "InvalidWithdrawalCooldown",
+      "msg": "Withdrawal cooldown cannot be negative"
+    },
+    {
+      "code": 6029,
+      "name": "WithdrawalNotClaimable",
+      "msg": "Pending withdrawal is still in its cooldown"
+    },
+    {
+      "code": 6030,
+      "name": "OutflowLimitExceeded",
+      "msg": "Withdrawal would exceed the vault's outflow limit for this window"
+    },
+    {
+      "code": 6031,
+      "name": "InvalidOutflowLimit",
+      "msg": "Outflow limit needs a positive window and at most 10000 basis points"
+    },
+    {
+      "code": 6032,
+      "name": "GuardianCannotLoosen",
+      "msg": "Guardian may only tighten the outflow limit"
+    },
+    {
+      "code": 6033,
+      "name": "InvalidRateCurve",
+      "msg": "Optimal utilization must be between 1 and 10000 basis points"
+    },
+    {
+      "code": 6034,
+      "name": "BorrowingDisabled",
+      "msg": "Borrowing is not enabled on this vault"
+    },
+    {
+      "code": 6035,
+      "name": "InvalidCollateralVault",
+      "msg": "Collateral vault must be a different vault from the one borrowed from"
+    },
+    {
+      "code": 6036,
+      "name": "InsufficientLiquidity",
+      "msg": "Not enough idle liquidity in the vault"
+    },
+    {
+      "code": 6037,
+      "name": "ExceedsMaxLtv",
+      "msg": "Debt would exceed the maximum loan-to-value ratio"
+    },
+    {
+      "code": 6038,
+      "name": "InvalidBorrowConfig",
+      "msg": "Max LTV must not exceed the liquidation threshold, which must leave room for the bonus"
+    },
+    {
+      "code": 6039,
+      "name": "PositionHealthy",
+      "msg": "Position is healthy and cannot be liquidated"
+    },
+    {
+      "code": 6040,
+      "name": "InvalidPriceFeed",
+      "msg": "Price feed does not match the vault"
+    },
+    {
+      "code": 6041,
+      "name": "MissingPriceFeed",
+      "msg": "Both vaults need a price feed to value collateral in another mint"
+    },
+    {
+      "code": 6042,
+      "name": "StalePrice",
+      "msg": "Price feed has not been updated recently enough"
+    },
+    {
+      "code": 6043,
+      "name": "InvalidRewardStream",
+      "msg": "Reward stream is invalid or does not match the vault"
+    },
+    {
+      "code": 6044,
+      "name": "NoRewardsToClaim",
+      "msg": "No rewards to claim"
+    },
+    {
+      "code": 6045,
+      "name": "FlashLoanActive",
+      "msg": "A flash loan is outstanding on this vault"
+    },
+    {
+      "code": 6046,
+      "name": "NoFlashLoan",
+      "msg": "No flash loan to repay"
+    },
+    {
+      "code": 6047,
+      "name": "InvalidFlashLoan",
+      "msg": "Flash loan must be repaid in full by a later flash_repay in the same transaction"
+    },
+    {
+      "code": 6048,
+      "name": "AllocationCapExceeded",
+      "msg": "Allocation would exceed the strategy's cap"
+    },
+    {
+      "code": 6049,
+      "name": "StrategyMismatch",
+      "msg": "Strategy moved a different amount than requested"
+    },
+    {
+      "code": 6050,
+      "name": "InvalidLossThreshold",
+      "msg": "Loss threshold cannot exceed 10000 bps"
+    },
+    {
+      "code": 6051,
+      "name": "OutstandingBorrows",
+      "msg": "Vault cannot be closed while loans against it are outstanding"
+    },
+    {
+      "code": 6052,
+      "name": "RewardStreamActive",
+      "msg": "Reward stream has not ended yet"
+    },
+    {
+      "code": 6053,
+      "name": "StrategyFundsOutstanding",
+      "msg": "Vault cannot be closed while funds are deployed to strategies"
+    },
+    {
+      "code": 6054,
+      "name": "InvalidDepositAccount",
+      "msg": "Strategy deposit account must hold the vault's mint and not belong to the vault"
+    },
+    {
+      "code": 6055,
+      "name": "VaultInsolvent",
+      "msg": "Vault has outstanding shares but no assets behind them"
+    },
+    {
+      "code": 6056,
+      "name": "CollateralNotAccepted",
+      "msg": "Borrow vault does not accept this collateral vault"
+    },
+    {
+      "code": 6057,
+      "name": "RewardsUnclaimed",
+      "msg": "Reward stream still owes shareholders rewards they have not claimed"
+    },
+    {
+      "code": 6058,
+      "name": "RewardStreamsOpen",
+      "msg": "Vault cannot be closed while reward streams are open"
+    },
+    {
+      "code": 6059,
+      "name": "OutstandingCollateral",
+      "msg": "Vault cannot be closed while its shares secure loans"
+    }
+  ]
+};
+
+export const IDL: DefiVault = {
+  "version": "0.1.0",
+  "name": "defi_vault",
+  "instructions": [
+    {
+      "name": "initializeVault",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "vaultId",
+          "type": "u64"
+        },
+        {
+          "name": "interestRate",
+          "type": "u64"
+        },
+        {
+          "name": "minDeposit",
+          "type": "u64"
+        },
+        {
+          "name": "interestModel",
+          "type": {
+            "defined": "InterestModel"
+          }
+        }
+      ]
+    },
+    {
+      "name": "deposit",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "depositLocked",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lockPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "termId",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "updateVaultParams",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "interestRate",
+          "type": "u64"
+        },
+        {
+          "name": "minDeposit",
+          "type": "u64"
+        },
+        {
+          "name": "interestModel",
+          "type": {
+            "defined": "InterestModel"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setRateCurve",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "rateCurve",
+          "type": {
+            "option": {
+              "defined": "RateCurve"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "setFees",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "fees",
+          "type": {
+            "defined": "FeeConfig"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setDepositCaps",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "maxTotalDeposits",
+          "type": {
+            "option": "u64"
+          }
+        },
+        {
+          "name": "maxPerUser",
+          "type": {
+            "option": "u64"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setWithdrawalCooldown",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "withdrawalCooldown",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "setGuardian",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "guardian",
+          "type": {
+            "option": "publicKey"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setOutflowLimit",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "outflowLimit",
+          "type": {
+            "defined": "OutflowLimit"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setLockTerm",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "termId",
+          "type": "u8"
+        },
+        {
+          "name": "duration",
+          "type": "i64"
+        },
+        {
+          "name": "interestRate",
+          "type": "u64"
+        },
+        {
+          "name": "earlyWithdrawalPenaltyBps",
+          "type": {
+            "option": "u16"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setPause",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "pausedFlags",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "proposeAuthority",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "newAuthority",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "acceptAuthority",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pendingAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "cancelAuthorityTransfer",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "fundReserve",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authorityTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "collectFees",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "withdraw",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "requestWithdraw",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pendingWithdrawal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "claimWithdraw",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pendingWithdrawal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "withdrawLocked",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lockPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closePosition",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": true,
+          "isSigner": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "borrow",
+      "accounts": [
+        {
+          "name": "borrowVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collateralVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collateralConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "borrowPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateralPriceFeed",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "borrowPriceFeed",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        }
+      ],
+      "args": [
+        {
+          "name": "collateralShares",
+          "type": "u64"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "repay",
+      "accounts": [
+        {
+          "name": "borrowVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "borrowPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "releaseCollateral",
+      "accounts": [
+        {
+          "name": "borrowVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collateralVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collateralConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "borrowPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateralPriceFeed",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "borrowPriceFeed",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        }
+      ],
+      "args": [
+        {
+          "name": "shares",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "liquidate",
+      "accounts": [
+        {
+          "name": "borrowVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collateralVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collateralConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "borrowPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "borrower",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liquidatorPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liquidator",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liquidatorTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateralPriceFeed",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "borrowPriceFeed",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        }
+      ],
+      "args": [
+        {
+          "name": "repayAmount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setBorrowConfig",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "borrowConfig",
+          "type": {
+            "defined": "BorrowConfig"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setCollateralConfig",
+      "accounts": [
+        {
+          "name": "borrowVault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateralVault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateralConfig",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "priceFeed",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "enabled",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setPriceFeed",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "priceFeed",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "initializePriceFeed",
+      "accounts": [
+        {
+          "name": "priceFeed",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "price",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "updatePrice",
+      "accounts": [
+        {
+          "name": "priceFeed",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "price",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "addRewardStream",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardStream",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rewardEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authorityRewardAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rewardTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "index",
+          "type": "u8"
+        },
+        {
+          "name": "emissionRate",
+          "type": "u64"
+        },
+        {
+          "name": "startTime",
+          "type": "i64"
+        },
+        {
+          "name": "endTime",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "extendRewardStream",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rewardStream",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rewardEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authorityRewardAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "rewardTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "newEndTime",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "sweepRewardStream",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rewardStream",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rewardEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authorityRewardAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "rewardTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closeRewardStream",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardStream",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rewardEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authorityRewardAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rewardTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimRewards",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardStream",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rewardEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userRewardAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "rewardTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimAllRewards",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "flashBorrow",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "borrower",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "borrowerTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "flashRepay",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payerTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "addStrategy",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "strategy",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "strategyProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "depositAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "allocationCap",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setAllocationCap",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "strategy",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "allocationCap",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setLossThreshold",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "lossThresholdBps",
+          "type": {
+            "option": "u16"
+          }
+        }
+      ]
+    },
+    {
+      "name": "allocate",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "strategy",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "strategyProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "depositAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "deallocate",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "strategy",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "strategyProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "depositAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "harvest",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "strategy",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "strategyProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "depositAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "report",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "strategy",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "gain",
+          "type": "u64"
+        },
+        {
+          "name": "loss",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "beginSunset",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "gracePeriod",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "closeVault",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authorityTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "getUserBalance",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userPosition",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [],
+      "returns": {
+        "defined": "UserBalanceInfo"
+      }
+    },
+    {
+      "name": "previewDeposit",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ],
+      "returns": "u64"
+    },
+    {
+      "name": "previewWithdraw",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ],
+      "returns": "u64"
+    },
+    {
+      "name": "convertToAssets",
+      "accounts": [
+        {
+          "name": "vault",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "shares",
+          "type": "u64"
+        }
+      ],
+      "returns": "u64"
+    }
+  ],
+  "accounts": [
+    {
+      "name": "vault",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "pendingAuthority",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "vaultId",
+            "type": "u64"
+          },
+          {
+            "name": "tokenVault",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenProgram",
+            "type": "publicKey"
+          },
+          {
+            "name": "rewardReserve",
+            "type": "publicKey"
+          },
+          {
+            "name": "treasury",
+            "type": "publicKey"
+          },
+          {
+            "name": "interestRate",
+            "type": "u64"
+          },
+          {
+            "name": "interestModel",
+            "type": {
+              "defined": "InterestModel"
+            }
+          },
+          {
+            "name": "rateCurve",
+            "type": {
+              "option": {
+                "defined": "RateCurve"
+              }
+            }
+          },
+          {
+            "name": "totalBorrowed",
+            "type": "u64"
+          },
+          {
+            "name": "totalCollateralShares",
+            "type": "u64"
+          },
+          {
+            "name": "borrowRate",
+            "type": "u64"
+          },
+          {
+            "name": "borrowIndex",
+            "type": "u128"
+          },
+          {
+            "name": "borrowIndexUpdatedAt",
+            "type": "i64"
+          },
+          {
+            "name": "borrowConfig",
+            "type": {
+              "defined": "BorrowConfig"
+            }
+          },
+          {
+            "name": "priceFeed",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "rewardStreams",
+            "type": {
+              "array": [
+                {
+                  "option": "publicKey"
+                },
+                4
+              ]
+            }
+          },
+          {
+            "name": "flashLoanAmount",
+            "type": "u64"
+          },
+          {
+            "name": "flashLoanFee",
+            "type": "u64"
+          },
+          {
+            "name": "totalAllocated",
+            "type": "u64"
+          },
+          {
+            "name": "lossThresholdBps",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "minDeposit",
+            "type": "u64"
+          },
+          {
+            "name": "fees",
+            "type": {
+              "defined": "FeeConfig"
+            }
+          },
+          {
+            "name": "maxTotalDeposits",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "maxPerUser",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "withdrawalCooldown",
+            "type": "i64"
+          },
+          {
+            "name": "guardian",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "outflowLimit",
+            "type": {
+              "defined": "OutflowLimit"
+            }
+          },
+          {
+            "name": "outflowWindowStart",
+            "type": "i64"
+          },
+          {
+            "name": "outflowWindowAmount",
+            "type": "u64"
+          },
+          {
+            "name": "outflowWindowTvl",
+            "type": "u64"
+          },
+          {
+            "name": "pausedFlags",
+            "type": "u8"
+          },
+          {
+            "name": "totalDeposited",
+            "type": "u64"
+          },
+          {
+            "name": "totalShares",
+            "type": "u64"
+          },
+          {
+            "name": "totalAccruedInterest",
+            "type": "u64"
+          },
+          {
+            "name": "lastAccrualTime",
+            "type": "i64"
+          },
+          {
+            "name": "totalLocked",
+            "type": "u64"
+          },
+          {
+            "name": "totalLockedInterest",
+            "type": "u64"
+          },
+          {
+            "name": "accruedVaultFees",
+            "type": "u64"
+          },
+          {
+            "name": "accruedReserveFees",
+            "type": "u64"
+          },
+          {
+            "name": "pendingPrincipal",
+            "type": "u64"
+          },
+          {
+            "name": "pendingInterest",
+            "type": "u64"
+          },
+          {
+            "name": "lockTerms",
+            "type": {
+              "array": [
+                {
+                  "defined": "LockTerm"
+                },
+                4
+              ]
+            }
+          },
+          {
+            "name": "latestUnlockTime",
+            "type": "i64"
+          },
+          {
+            "name": "sunsetDeadline",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "createdAt",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "userPosition",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "type": "publicKey"
+          },
+          {
+            "name": "vault",
+            "type": "publicKey"
+          },
+          {
+            "name": "shares",
+            "type": "u64"
+          },
+          {
+            "name": "depositedAmount",
+            "type": "u64"
+          },
+          {
+            "name": "lastUpdateTime",
+            "type": "i64"
+          },
+          {
+            "name": "depositCount",
+            "type": "u64"
+          },
+          {
+            "name": "withdrawCount",
+            "type": "u64"
+          },
+          {
+            "name": "lockCount",
+            "type": "u64"
+          },
+          {
+            "name": "openLocks",
+            "type": "u64"
+          },
+          {
+            "name": "lockedAmount",
+            "type": "u64"
+          },
+          {
+            "name": "rewardPerSharePaid",
+            "type": {
+              "array": [
+                "u128",
+                4
+              ]
+            }
+          },
+          {
+            "name": "pendingRewards",
+            "type": {
+              "array": [
+                "u64",
+                4
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "rewardStream",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "vault",
+            "type": "publicKey"
+          },
+          {
+            "name": "index",
+            "type": "u8"
+          },
+          {
+            "name": "rewardMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "rewardEscrow",
+            "type": "publicKey"
+          },
+          {
+            "name": "rewardTokenProgram",
+            "type": "publicKey"
+          },
+          {
+            "name": "emissionRate",
+            "type": "u64"
+          },
+          {
+            "name": "startTime",
+            "type": "i64"
+          },
+          {
+            "name": "endTime",
+            "type": "i64"
+          },
+          {
+            "name": "rewardPerShare",
+            "type": "u128"
+          },
+          {
+            "name": "unclaimedRewards",
+            "type": "u64"
+          },
+          {
+            "name": "lastUpdateTime",
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "strategy",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "vault",
+            "type": "publicKey"
+          },
+          {
+            "name": "strategyProgram",
+            "type": "publicKey"
+          },
+          {
+            "name": "depositAccount",
+            "type": "publicKey"
+          },
+          {
+            "name": "allocationCap",
+            "type": "u64"
+          },
+          {
+            "name": "allocated",
+            "type": "u64"
+          },
+          {
+            "name": "totalHarvested",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "lockPosition",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "type": "publicKey"
+          },
+          {
+            "name": "vault",
+            "type": "publicKey"
+          },
+          {
+            "name": "lockId",
+            "type": "u64"
+          },
+          {
+            "name": "termId",
+            "type": "u8"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "interestRate",
+            "type": "u64"
+          },
+          {
+            "name": "earlyWithdrawalPenaltyBps",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "startTime",
+            "type": "i64"
+          },
+          {
+            "name": "unlockTime",
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "borrowPosition",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "type": "publicKey"
+          },
+          {
+            "name": "borrowVault",
+            "type": "publicKey"
+          },
+          {
+            "name": "collateralVault",
+            "type": "publicKey"
+          },
+          {
+            "name": "collateralShares",
+            "type": "u64"
+          },
+          {
+            "name": "collateralBasis",
+            "type": "u64"
+          },
+          {
+            "name": "debt",
+            "type": "u64"
+          },
+          {
+            "name": "principal",
+            "type": "u64"
+          },
+          {
+            "name": "borrowIndex",
+            "type": "u128"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "collateralConfig",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "borrowVault",
+            "type": "publicKey"
+          },
+          {
+            "name": "collateralVault",
+            "type": "publicKey"
+          },
+          {
+            "name": "priceFeed",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "enabled",
+            "type": "bool"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "priceFeed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "decimals",
+            "type": "u8"
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "updatedAt",
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "pendingWithdrawal",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "type": "publicKey"
+          },
+          {
+            "name": "vault",
+            "type": "publicKey"
+          },
+          {
+            "name": "principalAmount",
+            "type": "u64"
+          },
+          {
+            "name": "interestAmount",
+            "type": "u64"
+          },
+          {
+            "name": "claimableAt",
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    }
+  ],
+  "types": [
+    {
+      "name": "FeeConfig",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "entryFeeBps",
+            "type": "u16"
+          },
+          {
+            "name": "exitFeeBps",
+            "type": "u16"
+          },
+          {
+            "name": "performanceFeeBps",
+            "type": "u16"
+          },
+          {
+            "name": "managementFeeBps",
+            "type": "u16"
+          },
+          {
+            "name": "flashLoanFeeBps",
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "RateCurve",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "baseRateBps",
+            "type": "u64"
+          },
+          {
+            "name": "slope1Bps",
+            "type": "u64"
+          },
+          {
+            "name": "optimalUtilizationBps",
+            "type": "u64"
+          },
+          {
+            "name": "slope2Bps",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "BorrowConfig",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "owner",
-            "type": "publicKey"
+            "name": "maxLtvBps",
+            "type": "u16"
+          },
+          {
+            "name": "liquidationThresholdBps",
+            "type": "u16"
+          },
+          {
+            "name": "liquidationBonusBps",
+            "type": "u16"
+          },
+          {
+            "name": "closeFactorBps",
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "OutflowLimit",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "window",
+            "type": "i64"
+          },
+          {
+            "name": "maxAmount",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "maxTvlBps",
+            "type": {
+              "option": "u16"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "LockTerm",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "duration",
+            "type": "i64"
+          },
+          {
+            "name": "interestRate",
+            "type": "u64"
+          },
+          {
+            "name": "earlyWithdrawalPenaltyBps",
+            "type": {
+              "option": "u16"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "UserBalanceInfo",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "depositedAmount",
+            "type": "u64"
+          },
+          {
+            "name": "accruedInterest",
+            "type": "u64"
+          },
+          {
+            "name": "totalBalance",
+            "type": "u64"
+          },
+          {
+            "name": "shares",
+            "type": "u64"
+          },
+          {
+            "name": "lastUpdateTime",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "FeeKind",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Entry"
+          },
+          {
+            "name": "Exit"
+          },
+          {
+            "name": "Performance"
+          },
+          {
+            "name": "Management"
+          },
+          {
+            "name": "FlashLoan"
+          }
+        ]
+      }
+    },
+    {
+      "name": "InterestModel",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Simple"
+          },
+          {
+            "name": "Compound",
+            "fields": [
+              {
+                "name": "period",
+                "type": "i64"
+              }
+            ]
+          },
+          {
+            "name": "Continuous"
+          }
+        ]
+      }
+    }
+  ],
+  "events": [
+    {
+      "name": "DepositEvent",
+      "fields": [
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "netAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "feeAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "shares",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "WithdrawEvent",
+      "fields": [
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "netAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "feeAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "shares",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "interestAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "principalAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PositionClosedEvent",
+      "fields": [
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "dustShares",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "WithdrawRequestedEvent",
+      "fields": [
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "feeAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "shares",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "interestAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "principalAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "claimableAt",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "WithdrawClaimedEvent",
+      "fields": [
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "interestAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "principalAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "netAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "WithdrawalCooldownUpdatedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "oldWithdrawalCooldown",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "newWithdrawalCooldown",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "LockedDepositEvent",
+      "fields": [
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "lockId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "termId",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "netAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "interestRate",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "unlockTime",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "LockedWithdrawEvent",
+      "fields": [
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "lockId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "principalAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "interestAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "penaltyAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "netAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "early",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "DepositCapsUpdatedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "maxTotalDeposits",
+          "type": {
+            "option": "u64"
+          },
+          "index": false
+        },
+        {
+          "name": "maxPerUser",
+          "type": {
+            "option": "u64"
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "LockTermUpdatedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "termId",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "duration",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "interestRate",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "earlyWithdrawalPenaltyBps",
+          "type": {
+            "option": "u16"
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "VaultParamsUpdatedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "oldInterestRate",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "newInterestRate",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "oldMinDeposit",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "newMinDeposit",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "oldInterestModel",
+          "type": {
+            "defined": "InterestModel"
+          },
+          "index": false
+        },
+        {
+          "name": "newInterestModel",
+          "type": {
+            "defined": "InterestModel"
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RateCurveUpdatedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "rateCurve",
+          "type": {
+            "option": {
+              "defined": "RateCurve"
+            }
+          },
+          "index": false
+        },
+        {
+          "name": "interestRate",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "FeesUpdatedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "oldFees",
+          "type": {
+            "defined": "FeeConfig"
           },
-          {
-            "name": "vault",
-            "type": "publicKey"
+          "index": false
+        },
+        {
+          "name": "newFees",
+          "type": {
+            "defined": "FeeConfig"
           },
-          {
-            "name": "depositedAmount",
-            "type": "u64"
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "FeeChargedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "user",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "kind",
+          "type": {
+            "defined": "FeeKind"
+          },
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "FeesCollectedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "treasury",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "outstanding",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "SunsetStartedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "deadline",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "VaultClosedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "sweptAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "GuardianUpdatedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "oldGuardian",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "newGuardian",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "OutflowLimitUpdatedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "updatedBy",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "oldOutflowLimit",
+          "type": {
+            "defined": "OutflowLimit"
+          },
+          "index": false
+        },
+        {
+          "name": "newOutflowLimit",
+          "type": {
+            "defined": "OutflowLimit"
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "BorrowEvent",
+      "fields": [
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "borrowVault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "collateralVault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "collateralShares",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "debt",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RepayEvent",
+      "fields": [
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "borrowVault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "interestAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "principalAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "debt",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "CollateralReleasedEvent",
+      "fields": [
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "borrowVault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "collateralVault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "shares",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "debt",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "BorrowConfigUpdatedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "borrowConfig",
+          "type": {
+            "defined": "BorrowConfig"
           },
-          {
-            "name": "accruedInterest",
-            "type": "u64"
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "CollateralConfigUpdatedEvent",
+      "fields": [
+        {
+          "name": "borrowVault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "collateralVault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "priceFeed",
+          "type": {
+            "option": "publicKey"
           },
-          {
-            "name": "lastUpdateTime",
-            "type": "i64"
+          "index": false
+        },
+        {
+          "name": "enabled",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RewardStreamAddedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "rewardStream",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "index",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "rewardMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "emissionRate",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "startTime",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "endTime",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RewardStreamSweptEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "rewardStream",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RewardStreamClosedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "rewardStream",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "sweptAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RewardStreamExtendedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "rewardStream",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "oldEndTime",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "newEndTime",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RewardsClaimedEvent",
+      "fields": [
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "rewardStream",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "StrategyAddedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "strategy",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "strategyProgram",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "depositAccount",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "allocationCap",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "AllocationCapUpdatedEvent",
+      "fields": [
+        {
+          "name": "strategy",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "oldCap",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "newCap",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "AllocationEvent",
+      "fields": [
+        {
+          "name": "strategy",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "allocated",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "DeallocationEvent",
+      "fields": [
+        {
+          "name": "strategy",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "gain",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "allocated",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "HarvestEvent",
+      "fields": [
+        {
+          "name": "strategy",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "totalHarvested",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "LossThresholdUpdatedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "oldThresholdBps",
+          "type": {
+            "option": "u16"
           },
-          {
-            "name": "depositCount",
-            "type": "u64"
+          "index": false
+        },
+        {
+          "name": "newThresholdBps",
+          "type": {
+            "option": "u16"
           },
-          {
-            "name": "withdrawCount",
-            "type": "u64"
-          }
-        ]
-      }
-    }
-  ],
-  "types": [
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "StrategyReportEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "strategy",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "gain",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "loss",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "allocated",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "totalAssets",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "withdrawOnly",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "FlashBorrowEvent",
+      "fields": [
+        {
+          "name": "borrower",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "fee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "FlashRepayEvent",
+      "fields": [
+        {
+          "name": "payer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "fee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "treasuryFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "LiquidationEvent",
+      "fields": [
+        {
+          "name": "liquidator",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "borrower",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "borrowVault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "collateralVault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "interestAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "principalAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "seizedShares",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "healthFactor",
+          "type": "u128",
+          "index": false
+        },
+        {
+          "name": "debt",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
     {
-      "name": "UserBalanceInfo",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "depositedAmount",
-            "type": "u64"
-          },
-          {
-            "name": "accruedInterest",
-            "type": "u64"
-          },
-          {
-            "name": "totalBalance",
-            "type": "u64"
+      "name": "PriceFeedUpdatedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "priceFeed",
+          "type": {
+            "option": "publicKey"
           },
-          {
-            "name": "lastUpdateTime",
-            "type": "i64"
-          }
-        ]
-      }
-    }
-  ],
-  "events": [
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
     {
-      "name": "DepositEvent",
+      "name": "PriceUpdatedEvent",
       "fields": [
         {
-          "name": "user",
+          "name": "priceFeed",
           "type": "publicKey",
           "index": false
         },
+        {
+          "name": "price",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PauseUpdatedEvent",
+      "fields": [
         {
           "name": "vault",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "amount",
-          "type": "u64",
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "pausedFlags",
+          "type": "u8",
           "index": false
         },
         {
@@ -625,23 +8761,103 @@ export const IDL: DefiVault = {
       ]
     },
     {
-      "name": "WithdrawEvent",
+      "name": "AuthorityProposedEvent",
       "fields": [
         {
-          "name": "user",
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "pendingAuthority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "AuthorityAcceptedEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "previousAuthority",
           "type": "publicKey",
           "index": false
         },
+        {
+          "name": "newAuthority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "AuthorityTransferCancelledEvent",
+      "fields": [
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "cancelledAuthority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ReserveFundedEvent",
+      "fields": [
         {
           "name": "vault",
           "type": "publicKey",
           "index": false
         },
+        {
+          "name": "funder",
+          "type": "publicKey",
+          "index": false
+        },
         {
           "name": "amount",
           "type": "u64",
           "index": false
         },
+        {
+          "name": "reserveBalance",
+          "type": "u64",
+          "index": false
+        },
         {
           "name": "timestamp",
           "type": "i64",
@@ -660,6 +8876,296 @@ export const IDL: DefiVault = {
       "code": 6001,
       "name": "InsufficientBalance",
       "msg": "Insufficient balance for withdrawal"
+    },
+    {
+      "code": 6002,
+      "name": "InvalidAmount",
+      "msg": "Amount must be greater than zero"
+    },
+    {
+      "code": 6003,
+      "name": "InsufficientReserve",
+      "msg": "Reward reserve cannot cover the accrued interest"
+    },
+    {
+      "code": 6004,
+      "name": "ZeroShares",
+      "msg": "Deposit is too small to mint any shares"
+    },
+    {
+      "code": 6005,
+      "name": "Unauthorized",
+      "msg": "Signer is not the vault authority"
+    },
+    {
+      "code": 6006,
+      "name": "InvalidAuthority",
+      "msg": "New authority must differ from the current authority"
+    },
+    {
+      "code": 6007,
+      "name": "NotPendingAuthority",
+      "msg": "Signer is not the pending vault authority"
+    },
+    {
+      "code": 6008,
+      "name": "NoPendingAuthority",
+      "msg": "No authority transfer is pending"
+    },
+    {
+      "code": 6009,
+      "name": "Paused",
+      "msg": "This vault operation is paused"
+    },
+    {
+      "code": 6010,
+      "name": "InvalidPauseFlags",
+      "msg": "Unknown pause flags"
+    },
+    {
+      "code": 6011,
+      "name": "MathOverflow",
+      "msg": "Math overflow"
+    },
+    {
+      "code": 6012,
+      "name": "InvalidTokenProgram",
+      "msg": "Token program does not match the vault's token program"
+    },
+    {
+      "code": 6013,
+      "name": "InvalidInterestModel",
+      "msg": "Compounding period must be greater than zero"
+    },
+    {
+      "code": 6014,
+      "name": "InvalidLockTerm",
+      "msg": "Lock term is not configured or its parameters are invalid"
+    },
+    {
+      "code": 6015,
+      "name": "LockNotMatured",
+      "msg": "Locked deposit has not reached its unlock time"
+    },
+    {
+      "code": 6016,
+      "name": "InvalidTreasury",
+      "msg": "Treasury account does not match the vault"
+    },
+    {
+      "code": 6017,
+      "name": "InvalidFee",
+      "msg": "Fee cannot exceed 10000 basis points"
+    },
+    {
+      "code": 6018,
+      "name": "PositionNotEmpty",
+      "msg": "Position still holds shares or open locks"
+    },
+    {
+      "code": 6019,
+      "name": "InvalidPosition",
+      "msg": "Position does not belong to this user and vault"
+    },
+    {
+      "code": 6020,
+      "name": "VaultSunsetting",
+      "msg": "Vault is being sunset"
+    },
+    {
+      "code": 6021,
+      "name": "VaultNotSunsetting",
+      "msg": "Vault has not begun its sunset"
+    },
+    {
+      "code": 6022,
+      "name": "InvalidSunsetDeadline",
+      "msg": "Sunset deadline must not be before any open lock matures"
+    },
+    {
+      "code": 6023,
+      "name": "SunsetInProgress",
+      "msg": "Depositors remain and the sunset deadline has not passed"
+    },
+    {
+      "code": 6024,
+      "name": "DepositCapExceeded",
+      "msg": "Deposit would exceed the vault's total deposit cap"
+    },
+    {
+      "code": 6025,
+      "name": "UserDepositCapExceeded",
+      "msg": "Deposit would exceed the per-user deposit cap"
+    },
+    {
+      "code": 6026,
+      "name": "WithdrawalCooldownActive",
+      "msg": "Withdrawals on this vault go through request_withdraw"
+    },
+    {
+      "code": 6027,
+      "name": "WithdrawalCooldownDisabled",
+      "msg": "Vault has no withdrawal cooldown; use withdraw"
+    },
+    {
+      "code": 6028,
+      "name": "InvalidWithdrawalCooldown",
+      "msg": "Withdrawal cooldown cannot be negative"
+    },
+    {
+      "code": 6029,
+      "name": "WithdrawalNotClaimable",
+      "msg": "Pending withdrawal is still in its cooldown"
+    },
+    {
+      "code": 6030,
+      "name": "OutflowLimitExceeded",
+      "msg": "Withdrawal would exceed the vault's outflow limit for this window"
+    },
+    {
+      "code": 6031,
+      "name": "InvalidOutflowLimit",
+      "msg": "Outflow limit needs a positive window and at most 10000 basis points"
+    },
+    {
+      "code": 6032,
+      "name": "GuardianCannotLoosen",
+      "msg": "Guardian may only tighten the outflow limit"
+    },
+    {
+      "code": 6033,
+      "name": "InvalidRateCurve",
+      "msg": "Optimal utilization must be between 1 and 10000 basis points"
+    },
+    {
+      "code": 6034,
+      "name": "BorrowingDisabled",
+      "msg": "Borrowing is not enabled on this vault"
+    },
+    {
+      "code": 6035,
+      "name": "InvalidCollateralVault",
+      "msg": "Collateral vault must be a different vault from the one borrowed from"
+    },
+    {
+      "code": 6036,
+      "name": "InsufficientLiquidity",
+      "msg": "Not enough idle liquidity in the vault"
+    },
+    {
+      "code": 6037,
+      "name": "ExceedsMaxLtv",
+      "msg": "Debt would exceed the maximum loan-to-value ratio"
+    },
+    {
+      "code": 6038,
+      "name": "InvalidBorrowConfig",
+      "msg": "Max LTV must not exceed the liquidation threshold, which must leave room for the bonus"
+    },
+    {
+      "code": 6039,
+      "name": "PositionHealthy",
+      "msg": "Position is healthy and cannot be liquidated"
+    },
+    {
+      "code": 6040,
+      "name": "InvalidPriceFeed",
+      "msg": "Price feed does not match the vault"
+    },
+    {
+      "code": 6041,
+      "name": "MissingPriceFeed",
+      "msg": "Both vaults need a price feed to value collateral in another mint"
+    },
+    {
+      "code": 6042,
+      "name": "StalePrice",
+      "msg": "Price feed has not been updated recently enough"
+    },
+    {
+      "code": 6043,
+      "name": "InvalidRewardStream",
+      "msg": "Reward stream is invalid or does not match the vault"
+    },
+    {
+      "code": 6044,
+      "name": "NoRewardsToClaim",
+      "msg": "No rewards to claim"
+    },
+    {
+      "code": 6045,
+      "name": "FlashLoanActive",
+      "msg": "A flash loan is outstanding on this vault"
+    },
+    {
+      "code": 6046,
+      "name": "NoFlashLoan",
+      "msg": "No flash loan to repay"
+    },
+    {
+      "code": 6047,
+      "name": "InvalidFlashLoan",
+      "msg": "Flash loan must be repaid in full by a later flash_repay in the same transaction"
+    },
+    {
+      "code": 6048,
+      "name": "AllocationCapExceeded",
+      "msg": "Allocation would exceed the strategy's cap"
+    },
+    {
+      "code": 6049,
+      "name": "StrategyMismatch",
+      "msg": "Strategy moved a different amount than requested"
+    },
+    {
+      "code": 6050,
+      "name": "InvalidLossThreshold",
+      "msg": "Loss threshold cannot exceed 10000 bps"
+    },
+    {
+      "code": 6051,
+      "name": "OutstandingBorrows",
+      "msg": "Vault cannot be closed while loans against it are outstanding"
+    },
+    {
+      "code": 6052,
+      "name": "RewardStreamActive",
+      "msg": "Reward stream has not ended yet"
+    },
+    {
+      "code": 6053,
+      "name": "StrategyFundsOutstanding",
+      "msg": "Vault cannot be closed while funds are deployed to strategies"
+    },
+    {
+      "code": 6054,
+      "name": "InvalidDepositAccount",
+      "msg": "Strategy deposit account must hold the vault's mint and not belong to the vault"
+    },
+    {
+      "code": 6055,
+      "name": "VaultInsolvent",
+      "msg": "Vault has outstanding shares but no assets behind them"
+    },
+    {
+      "code": 6056,
+      "name": "CollateralNotAccepted",
+      "msg": "Borrow vault does not accept this collateral vault"
+    },
+    {
+      "code": 6057,
+      "name": "RewardsUnclaimed",
+      "msg": "Reward stream still owes shareholders rewards they have not claimed"
+    },
+    {
+      "code": 6058,
+      "name": "RewardStreamsOpen",
+      "msg": "Vault cannot be closed while reward streams are open"
+    },
+    {
+      "code": 6059,
+      "name": "OutstandingCollateral",
+      "msg": "Vault cannot be closed while its shares secure loans"
     }
   ]
 };
\ No newline at end of file
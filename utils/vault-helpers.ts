@@ -5,6 +5,8 @@ import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
   getAssociatedTokenAddress,
   createAssociatedTokenAccountInstruction,
+  createAssociatedTokenAccountIdempotentInstruction,
+  getAssociatedTokenAddressSync,
   getAccount
 } from '@solana/spl-token';
 import { DefiVault } from '@/types/defi_vault';
@@ -44,6 +46,11 @@ export async function ensureVaultExists(
     PROGRAM_ID
   );
 
+  const [rewardReservePda] = PublicKey.findProgramAddressSync(
+    [Buffer.from('vault-reserve'), mint.toBuffer()],
+    PROGRAM_ID
+  );
+
   try {
     // Check if vault already exists
     await program.account.vault.fetch(vaultPda);
@@ -60,14 +67,22 @@ export async function ensureVaultExists(
 
       const transaction = new Transaction();
 
+      // The initializer's token account is the vault treasury, which collects fees and penalties
+      const treasury = getAssociatedTokenAddressSync(mint, payer, false, TOKEN_PROGRAM_ID);
+      transaction.add(
+        createAssociatedTokenAccountIdempotentInstruction(payer, treasury, payer, mint, TOKEN_PROGRAM_ID)
+      );
+
       // Add initialize vault instruction using standard SPL token
       const initializeInstruction = await program.methods
-        .initializeVault(new BN(interestRate), new BN(minDeposit))
+        .initializeVault(new BN(0), new BN(interestRate), new BN(minDeposit), { simple: {} })
         .accounts({
           vault: vaultPda,
           authority: payer,
           tokenMint: mint,
           tokenVault: vaultTokenPda,
+          rewardReserve: rewardReservePda,
+          treasury: treasury,
           tokenProgram: TOKEN_PROGRAM_ID, // Use standard SPL token program
           systemProgram: SystemProgram.programId,
           rent: SystemProgram.programId,